@@ -0,0 +1,173 @@
+// album.rs
+use crate::logs::{self, LogSender};
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+use std::{env, fs};
+
+// Artist/Album folder, from playlist fields so tracks and the cover land in the same place
+const ALBUM_DIR: &str =
+    "%(playlist_uploader,playlist_channel,uploader)s/%(playlist_title,playlist)s";
+
+// Output template for the tracks, numbered from the playlist index
+pub fn track_template() -> String {
+    format!("{}/%(playlist_index)02d - %(title)s.%(ext)s", ALBUM_DIR)
+}
+
+// Track thumbnails are only a side effect of --write-thumbnail, they go here and are deleted
+fn track_thumbnail_dir() -> PathBuf {
+    env::temp_dir().join(format!("ytdlprustygui-thumbnails-{}", process::id()))
+}
+
+// yt-dlp arguments tagging each track and saving the playlist thumbnail next to the tracks
+pub fn args(output_dir: &str) -> Vec<String> {
+    let mut args = vec![
+        "--write-thumbnail".to_string(),
+        "-o".to_string(),
+        format!("pl_thumbnail:{}/{}/cover.%(ext)s", output_dir, ALBUM_DIR),
+        "-o".to_string(),
+        format!(
+            "thumbnail:{}/%(id)s.%(ext)s",
+            track_thumbnail_dir().to_string_lossy()
+        ),
+        "--embed-metadata".to_string(),
+    ];
+
+    // meta_ fields override what --embed-metadata would write
+    for mapping in [
+        "playlist_index:%(meta_track)s",
+        "%(album,playlist_title,playlist)s:%(meta_album)s",
+        "%(artist,playlist_uploader,uploader)s:%(meta_artist)s",
+        "%(playlist_uploader,playlist_channel,uploader)s:%(meta_album_artist)s",
+        "%(release_year,release_date,upload_date)s:(?P<meta_date>\\d{4})",
+    ] {
+        args.push("--parse-metadata".to_string());
+        args.push(mapping.to_string());
+    }
+
+    args
+}
+
+// Turn the playlist thumbnail into cover.jpg and embed it in every track that can hold a picture
+pub fn finish_cover(tracks: &[PathBuf], log: &LogSender) -> Result<Option<PathBuf>, String> {
+    let _ = fs::remove_dir_all(track_thumbnail_dir());
+
+    let Some(folder) = tracks.first().and_then(|track| track.parent()) else {
+        return Ok(None);
+    };
+
+    // yt-dlp keeps the thumbnail's own format, usually webp
+    let cover = folder.join("cover.jpg");
+    if !cover.exists() {
+        let Some(source) = find_cover(folder) else {
+            return Ok(None);
+        };
+
+        let mut cmd = Command::new("ffmpeg");
+        cmd.args(["-hide_banner", "-nostdin", "-y", "-i"])
+            .arg(&source)
+            .arg(&cover);
+        let output = logs::run_logged(&mut cmd, "Album cover", log, false)
+            .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+        if !output.success {
+            return Err(format!(
+                "Failed to convert the cover: {}",
+                output.error_message()
+            ));
+        }
+        let _ = fs::remove_file(&source);
+    }
+
+    for track in tracks {
+        embed_cover(track, &cover, log)?;
+    }
+
+    Ok(Some(cover))
+}
+
+fn find_cover(folder: &Path) -> Option<PathBuf> {
+    fs::read_dir(folder)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .find(|path| path.file_stem().is_some_and(|stem| stem == "cover"))
+}
+
+fn embed_cover(track: &Path, cover: &Path, log: &LogSender) -> Result<(), String> {
+    // Ogg and WebM have no attached picture stream ffmpeg can write
+    let extension = track
+        .extension()
+        .map(|extension| extension.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if !["mp3", "m4a", "flac"].contains(&extension.as_str()) {
+        return Ok(());
+    }
+
+    let stem = track.file_stem().unwrap_or_default().to_string_lossy();
+    let output = track.with_file_name(format!(".{}.cover.{}", stem, extension));
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-hide_banner", "-nostdin", "-y", "-i"])
+        .arg(track)
+        .arg("-i")
+        .arg(cover)
+        .args([
+            "-map",
+            "0:a",
+            "-map",
+            "1:v",
+            "-c",
+            "copy",
+            "-id3v2_version",
+            "3",
+            "-disposition:v",
+            "attached_pic",
+        ])
+        .arg(&output);
+
+    let result = logs::run_logged(&mut cmd, "Album cover", log, false)
+        .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+    if !result.success {
+        let _ = fs::remove_file(&output);
+        return Err(format!(
+            "Failed to embed the cover in {}: {}",
+            track.display(),
+            result.error_message()
+        ));
+    }
+
+    fs::rename(&output, track).map_err(|e| format!("Failed to replace {}: {}", track.display(), e))
+}
+
+// Write an extended M3U next to the tracks, in download order, returns its path
+pub fn write_playlist(tracks: &[PathBuf]) -> Result<Option<PathBuf>, String> {
+    let Some(folder) = tracks.first().and_then(|track| track.parent()) else {
+        return Ok(None);
+    };
+
+    let name = folder
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "playlist".to_string());
+    let path = folder.join(format!("{}.m3u", name));
+
+    let mut contents = String::from("#EXTM3U\n");
+    for track in tracks {
+        let title = track
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        contents.push_str(&format!("#EXTINF:-1,{}\n", title));
+        contents.push_str(&relative_to(track, folder));
+        contents.push('\n');
+    }
+
+    fs::write(&path, contents).map_err(|e| format!("Failed to write playlist: {}", e))?;
+    Ok(Some(path))
+}
+
+fn relative_to(path: &Path, folder: &Path) -> String {
+    path.strip_prefix(folder)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
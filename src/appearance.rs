@@ -0,0 +1,188 @@
+// appearance.rs
+use eframe::egui::{self, Color32, FontData, FontDefinitions, FontFamily, ThemePreference};
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, sync::Arc};
+
+// Fonts with Japanese and Chinese glyphs that ship with common systems
+const CJK_FONTS: &[&str] = &[
+    "/usr/share/fonts/opentype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/google-noto-cjk/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/truetype/noto/NotoSansCJK-Regular.ttc",
+    "/usr/share/fonts/adobe-source-han-sans/SourceHanSans-Regular.ttc",
+    "/usr/share/fonts/truetype/droid/DroidSansFallbackFull.ttf",
+    "/usr/share/fonts/wenquanyi/wqy-microhei/wqy-microhei.ttc",
+    "/System/Library/Fonts/Hiragino Sans GB.ttc",
+    "/System/Library/Fonts/ヒラギノ角ゴシック W3.ttc",
+    "/Library/Fonts/Arial Unicode.ttf",
+    "C:\\Windows\\Fonts\\msyh.ttc",
+    "C:\\Windows\\Fonts\\YuGothM.ttc",
+    "C:\\Windows\\Fonts\\msgothic.ttc",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum Theme {
+    #[default]
+    System, // Follow the desktop's light or dark mode
+    Light,
+    Dark,
+}
+
+impl Theme {
+    pub const ALL: [Theme; 3] = [Theme::System, Theme::Light, Theme::Dark];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Theme::System => "System",
+            Theme::Light => "Light",
+            Theme::Dark => "Dark",
+        }
+    }
+
+    fn preference(self) -> ThemePreference {
+        match self {
+            Theme::System => ThemePreference::System,
+            Theme::Light => ThemePreference::Light,
+            Theme::Dark => ThemePreference::Dark,
+        }
+    }
+}
+
+// What a status message is about, shown in the colour picked for it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Status {
+    Info,
+    Working,
+    Success,
+    Warning,
+    Error,
+}
+
+// sRGB colours of the status kinds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StatusColors {
+    pub info: [u8; 3],
+    pub working: [u8; 3],
+    pub success: [u8; 3],
+    pub warning: [u8; 3],
+    pub error: [u8; 3],
+}
+
+impl Default for StatusColors {
+    fn default() -> Self {
+        Self {
+            info: [160, 160, 160],
+            working: [255, 255, 0],
+            success: [0, 255, 0],
+            warning: [255, 255, 0],
+            error: [255, 0, 0],
+        }
+    }
+}
+
+impl StatusColors {
+    // Okabe-Ito colours, distinguishable with the common kinds of colour blindness
+    pub fn colour_blind() -> Self {
+        Self {
+            info: [160, 160, 160],
+            working: [86, 180, 233],
+            success: [0, 114, 178],
+            warning: [230, 159, 0],
+            error: [213, 94, 0],
+        }
+    }
+
+    pub fn color(&self, status: Status) -> Color32 {
+        let [r, g, b] = match status {
+            Status::Info => self.info,
+            Status::Working => self.working,
+            Status::Success => self.success,
+            Status::Warning => self.warning,
+            Status::Error => self.error,
+        };
+        Color32::from_rgb(r, g, b)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppearanceSettings {
+    pub theme: Theme,
+    pub zoom: f32,
+    pub font_file: String,  // Font used for text egui's own fonts don't cover
+    pub cjk_fallback: bool, // Look for an installed CJK font as well
+    pub status_colors: StatusColors,
+}
+
+impl Default for AppearanceSettings {
+    fn default() -> Self {
+        Self {
+            theme: Theme::System,
+            zoom: 1.0,
+            font_file: String::new(),
+            cjk_fallback: true,
+            status_colors: StatusColors::default(),
+        }
+    }
+}
+
+impl AppearanceSettings {
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_theme(self.theme.preference());
+        ctx.set_zoom_factor(self.zoom);
+    }
+
+    // Whether the fonts have to be loaded again after a change
+    pub fn fonts_changed(&self, other: &AppearanceSettings) -> bool {
+        self.font_file != other.font_file || self.cjk_fallback != other.cjk_fallback
+    }
+}
+
+// First CJK font found on this system
+pub fn find_cjk_font() -> Option<&'static str> {
+    CJK_FONTS
+        .iter()
+        .find(|path| Path::new(path).exists())
+        .copied()
+}
+
+// Font definitions to install and the error of a font file that couldn't be read
+pub type FontsResult = (FontDefinitions, Result<(), String>);
+
+// egui's fonts followed by the chosen and the CJK font, so their glyphs fill the gaps.
+// Reading a large font collection takes a while, so this runs off the UI thread
+pub fn read_fonts(settings: &AppearanceSettings) -> FontsResult {
+    let mut fonts = FontDefinitions::default();
+    let mut error = None;
+
+    let mut files = Vec::new();
+    if !settings.font_file.trim().is_empty() {
+        files.push(settings.font_file.trim().to_string());
+    }
+    if settings.cjk_fallback
+        && let Some(path) = find_cjk_font()
+    {
+        files.push(path.to_string());
+    }
+
+    for (index, file) in files.iter().enumerate() {
+        let data = match fs::read(file) {
+            Ok(data) => data,
+            Err(e) => {
+                error = Some(format!("Failed to load font {}: {}", file, e));
+                continue;
+            }
+        };
+
+        let name = format!("fallback_{}", index);
+        fonts
+            .font_data
+            .insert(name.clone(), Arc::new(FontData::from_owned(data)));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            fonts.families.entry(family).or_default().push(name.clone());
+        }
+    }
+
+    (fonts, error.map_or(Ok(()), Err))
+}
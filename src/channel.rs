@@ -0,0 +1,179 @@
+// channel.rs
+use crate::logs::{self, LogSender};
+use serde_json::Value;
+use std::cmp::Reverse;
+use std::process::Command;
+
+pub type ChannelResult = Result<Vec<ChannelEntry>, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelTab {
+    Videos,
+    Shorts,
+    Streams,
+    Playlists,
+}
+
+impl ChannelTab {
+    pub const ALL: [ChannelTab; 4] = [
+        ChannelTab::Videos,
+        ChannelTab::Shorts,
+        ChannelTab::Streams,
+        ChannelTab::Playlists,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChannelTab::Videos => "Videos",
+            ChannelTab::Shorts => "Shorts",
+            ChannelTab::Streams => "Live",
+            ChannelTab::Playlists => "Playlists",
+        }
+    }
+
+    fn path(&self) -> &'static str {
+        match self {
+            ChannelTab::Videos => "videos",
+            ChannelTab::Shorts => "shorts",
+            ChannelTab::Streams => "streams",
+            ChannelTab::Playlists => "playlists",
+        }
+    }
+
+    // The tab's URL, whichever tab (if any) the channel URL pointed at
+    pub fn url(&self, channel_url: &str) -> String {
+        let mut base = channel_url.trim().trim_end_matches('/');
+        for tab in ChannelTab::ALL {
+            if let Some(stripped) = base.strip_suffix(&format!("/{}", tab.path())) {
+                base = stripped;
+            }
+        }
+        for other in ["/featured", "/community", "/about"] {
+            base = base.strip_suffix(other).unwrap_or(base);
+        }
+
+        format!("{}/{}", base, self.path())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChannelSort {
+    TabOrder, // As listed by the site, newest first for most tabs
+    Newest,
+    Oldest,
+    MostViewed,
+}
+
+impl ChannelSort {
+    pub const ALL: [ChannelSort; 4] = [
+        ChannelSort::TabOrder,
+        ChannelSort::Newest,
+        ChannelSort::Oldest,
+        ChannelSort::MostViewed,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChannelSort::TabOrder => "Channel order",
+            ChannelSort::Newest => "Newest first",
+            ChannelSort::Oldest => "Oldest first",
+            ChannelSort::MostViewed => "Most viewed",
+        }
+    }
+
+    pub fn sort(&self, entries: &mut [ChannelEntry]) {
+        match self {
+            ChannelSort::TabOrder => entries.sort_by_key(|entry| entry.index),
+            ChannelSort::Newest => entries.sort_by(|a, b| b.upload_date.cmp(&a.upload_date)),
+            ChannelSort::Oldest => entries.sort_by(|a, b| a.upload_date.cmp(&b.upload_date)),
+            ChannelSort::MostViewed => entries.sort_by_key(|entry| Reverse(entry.view_count)),
+        }
+    }
+}
+
+// One video or playlist listed on a channel tab
+#[derive(Debug, Clone)]
+pub struct ChannelEntry {
+    pub index: usize, // Position on the tab
+    pub title: String,
+    pub url: String,
+    pub upload_date: Option<String>, // YYYYMMDD, approximate for YouTube tabs
+    pub view_count: Option<u64>,
+    pub duration: Option<f64>, // Seconds
+}
+
+// Fetch one page of a channel tab without resolving each entry
+pub fn fetch(
+    tab_url: &str,
+    page: u32,
+    page_size: u32,
+    common_args: &[String],
+    log: &LogSender,
+) -> ChannelResult {
+    let first = page * page_size + 1;
+    let last = first + page_size - 1;
+
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args)
+        .args(["--flat-playlist", "-J"])
+        .args(["--playlist-items", &format!("{}:{}", first, last)])
+        // Flat YouTube tabs have no dates unless they are estimated from "3 weeks ago"
+        .args(["--extractor-args", "youtubetab:approximate_date"])
+        .arg(tab_url);
+
+    let output = logs::run_logged(&mut cmd, "Channel", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!(
+            "Failed to list channel: {}",
+            output.error_message()
+        ));
+    }
+
+    parse_entries(&output.stdout.join("\n"), first as usize)
+}
+
+// Entries of a -J playlist, numbered from first, a single video becomes one entry
+pub fn parse_entries(output: &str, first: usize) -> ChannelResult {
+    let json: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))?;
+
+    let Some(entries) = json.get("entries").and_then(Value::as_array) else {
+        return Ok(parse_entry(first, &json).into_iter().collect());
+    };
+
+    Ok(entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| parse_entry(first + index, entry))
+        .collect())
+}
+
+fn parse_entry(index: usize, entry: &Value) -> Option<ChannelEntry> {
+    let text = |key: &str| entry.get(key).and_then(Value::as_str);
+    let url = text("webpage_url").or_else(|| text("url"))?.to_string();
+
+    Some(ChannelEntry {
+        index,
+        title: text("title").unwrap_or(&url).to_string(),
+        upload_date: text("upload_date").map(|date| date.to_string()),
+        view_count: entry.get("view_count").and_then(Value::as_u64),
+        duration: entry.get("duration").and_then(Value::as_f64),
+        url,
+    })
+}
+
+// Accept 2024-05-31 as well as yt-dlp's own 20240531
+pub fn parse_date(text: &str) -> Option<String> {
+    let digits: String = text.trim().chars().filter(|c| *c != '-').collect();
+    (digits.len() == 8 && digits.chars().all(|c| c.is_ascii_digit())).then_some(digits)
+}
+
+// 20240531 as 2024-05-31
+pub fn format_date(date: &str) -> String {
+    if date.len() == 8 {
+        format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..])
+    } else {
+        date.to_string()
+    }
+}
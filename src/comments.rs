@@ -0,0 +1,278 @@
+// comments.rs
+use crate::export::csv_field;
+use crate::logs::{self, LogSender};
+use chrono::{Local, TimeZone};
+use serde_json::{Map, Value, json};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::{fs, process::Command};
+
+pub type CommentsResult = Result<(usize, Vec<PathBuf>), String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentSort {
+    Top,
+    Newest,
+}
+
+// How many comments to fetch and in which order
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommentOptions {
+    pub max_comments: u32, // 0 fetches all of them
+    pub max_replies: u32,  // Per thread, 0 fetches all
+    pub sort: CommentSort,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        Self {
+            max_comments: 500,
+            max_replies: 0,
+            sort: CommentSort::Top,
+        }
+    }
+}
+
+impl CommentOptions {
+    fn extractor_args(&self) -> String {
+        let limit = |n: u32| {
+            if n == 0 {
+                "all".to_string()
+            } else {
+                n.to_string()
+            }
+        };
+        let sort = match self.sort {
+            CommentSort::Top => "top",
+            CommentSort::Newest => "new",
+        };
+
+        // max_comments is max-comments,max-parents,max-replies,max-replies-per-thread
+        format!(
+            "youtube:comment_sort={};max_comments={},all,all,{}",
+            sort,
+            limit(self.max_comments),
+            limit(self.max_replies)
+        )
+    }
+}
+
+// Fetch the comments of a video (or every video of a playlist) and write a CSV and a threaded
+// JSON file per video into the folder, returns the number of comments and the files written
+pub fn export(
+    url: &str,
+    options: &CommentOptions,
+    folder: &Path,
+    common_args: &[String],
+    log: &LogSender,
+) -> CommentsResult {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args)
+        .args(["-J", "--write-comments"])
+        .args(["--extractor-args", &options.extractor_args()])
+        .arg(url);
+
+    let output = logs::run_logged(&mut cmd, "Comments", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!(
+            "Fetching comments failed: {}",
+            output.error_message()
+        ));
+    }
+
+    let json: Value = serde_json::from_str(&output.stdout.join("\n"))
+        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))?;
+    let videos = match json.get("entries").and_then(Value::as_array) {
+        Some(entries) => entries.iter().collect(),
+        None => vec![&json],
+    };
+
+    let mut total = 0;
+    let mut files = Vec::new();
+    for video in videos {
+        let id = video.get("id").and_then(Value::as_str).unwrap_or("video");
+        let comments: Vec<Value> = video
+            .get("comments")
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+
+        let csv_path = folder.join(format!("{}.comments.csv", id));
+        fs::write(&csv_path, to_csv(&comments))
+            .map_err(|e| format!("Failed to write {}: {}", csv_path.display(), e))?;
+
+        let json_path = folder.join(format!("{}.comments.json", id));
+        let threaded = json!({
+            "id": id,
+            "title": video.get("title"),
+            "url": video.get("webpage_url"),
+            "comment_count": comments.len(),
+            "comments": threads(&comments),
+        });
+        let contents = serde_json::to_string_pretty(&threaded)
+            .map_err(|e| format!("Failed to serialize comments: {}", e))?;
+        fs::write(&json_path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", json_path.display(), e))?;
+
+        total += comments.len();
+        files.push(csv_path);
+        files.push(json_path);
+    }
+
+    Ok((total, files))
+}
+
+fn text<'a>(comment: &'a Value, key: &str) -> &'a str {
+    comment.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+fn format_timestamp(comment: &Value) -> String {
+    comment
+        .get("timestamp")
+        .and_then(Value::as_i64)
+        .and_then(|timestamp| Local.timestamp_opt(timestamp, 0).single())
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default()
+}
+
+// One row per comment, replies point at their parent
+fn to_csv(comments: &[Value]) -> String {
+    let mut csv =
+        String::from("id,parent,author,author_id,timestamp,likes,pinned,by_uploader,text\n");
+
+    for comment in comments {
+        let flag = |key: &str| {
+            comment
+                .get(key)
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+                .to_string()
+        };
+        let fields = [
+            text(comment, "id").to_string(),
+            text(comment, "parent").to_string(),
+            text(comment, "author").to_string(),
+            text(comment, "author_id").to_string(),
+            format_timestamp(comment),
+            comment
+                .get("like_count")
+                .and_then(Value::as_u64)
+                .unwrap_or(0)
+                .to_string(),
+            flag("is_pinned"),
+            flag("author_is_uploader"),
+            text(comment, "text").to_string(),
+        ];
+
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// Nest replies under the comment they answer, top level comments have "root" as parent
+fn threads(comments: &[Value]) -> Vec<Value> {
+    let node = |comment: &Value| {
+        let mut node = Map::new();
+        node.insert("id".to_string(), json!(text(comment, "id")));
+        node.insert("author".to_string(), json!(text(comment, "author")));
+        node.insert("author_id".to_string(), json!(text(comment, "author_id")));
+        node.insert("timestamp".to_string(), json!(format_timestamp(comment)));
+        node.insert("likes".to_string(), json!(comment.get("like_count")));
+        node.insert("pinned".to_string(), json!(comment.get("is_pinned")));
+        node.insert(
+            "by_uploader".to_string(),
+            json!(comment.get("author_is_uploader")),
+        );
+        node.insert("text".to_string(), json!(text(comment, "text")));
+        node
+    };
+
+    let mut roots: Vec<Map<String, Value>> = Vec::new();
+    let mut root_index: HashMap<&str, usize> = HashMap::new();
+    let mut orphans = Vec::new();
+
+    for comment in comments {
+        let parent = text(comment, "parent");
+        if parent.is_empty() || parent == "root" {
+            let mut root = node(comment);
+            root.insert("replies".to_string(), json!([]));
+            root_index.insert(text(comment, "id"), roots.len());
+            roots.push(root);
+            continue;
+        }
+
+        let replies = root_index
+            .get(parent)
+            .and_then(|index| roots[*index].get_mut("replies"))
+            .and_then(Value::as_array_mut);
+        match replies {
+            Some(replies) => replies.push(Value::Object(node(comment))),
+            // A reply whose parent wasn't fetched still shouldn't get lost
+            None => orphans.push(Value::Object(node(comment))),
+        }
+    }
+
+    let mut threads: Vec<Value> = roots.into_iter().map(Value::Object).collect();
+    threads.extend(orphans);
+    threads
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Comments as yt-dlp lists them: flat, replies right after their thread with "parent" set
+    fn sample() -> Vec<Value> {
+        vec![
+            json!({"id": "a", "parent": "root", "author": "@first", "text": "Great video",
+                "like_count": 12, "is_pinned": true}),
+            json!({"id": "a.1", "parent": "a", "author": "@uploader", "text": "Thanks, glad you liked it",
+                "author_is_uploader": true}),
+            json!({"id": "b", "parent": "root", "author": "@second", "text": "Line one\nsaid \"hi\", twice"}),
+            json!({"id": "c.1", "parent": "c", "author": "@late", "text": "Reply to a missing thread"}),
+        ]
+    }
+
+    #[test]
+    fn replies_are_nested_under_their_thread() {
+        let threads = threads(&sample());
+        let ids: Vec<&str> = threads.iter().map(|thread| text(thread, "id")).collect();
+        assert_eq!(ids, vec!["a", "b", "c.1"]);
+
+        let replies = threads[0]["replies"].as_array().unwrap();
+        assert_eq!(replies.len(), 1);
+        assert_eq!(text(&replies[0], "id"), "a.1");
+        assert_eq!(replies[0]["by_uploader"], json!(true));
+        assert_eq!(threads[1]["replies"], json!([]));
+    }
+
+    #[test]
+    fn orphaned_replies_are_kept() {
+        let threads = threads(&sample());
+        let orphan = threads.last().unwrap();
+        assert_eq!(text(orphan, "text"), "Reply to a missing thread");
+        assert!(orphan.get("replies").is_none());
+    }
+
+    #[test]
+    fn csv_has_a_row_per_comment_with_quoted_text() {
+        let csv = to_csv(&sample());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "id,parent,author,author_id,timestamp,likes,pinned,by_uploader,text"
+        );
+        assert_eq!(lines[1], "a,root,@first,,,12,true,false,Great video");
+        assert_eq!(
+            lines[2],
+            "a.1,a,@uploader,,,0,false,true,\"Thanks, glad you liked it\""
+        );
+        assert_eq!(lines[3], "b,root,@second,,,0,false,false,\"Line one");
+        assert_eq!(lines[4], "said \"\"hi\"\", twice\"");
+        assert_eq!(lines.len(), 6);
+    }
+}
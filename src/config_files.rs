@@ -0,0 +1,39 @@
+// config_files.rs
+use crate::logs::{self, LogSender};
+use std::process::Command;
+
+pub type ConfigResult = Result<Vec<String>, String>;
+
+// Arguments selecting which yt-dlp configuration files are read
+pub fn args(location: &str, ignore_config: bool) -> Vec<String> {
+    let mut args = Vec::new();
+
+    // --ignore-config still loads the files given to --config-locations
+    if ignore_config {
+        args.push("--ignore-config".to_string());
+    }
+    if !location.trim().is_empty() {
+        args.push("--config-locations".to_string());
+        args.push(location.trim().to_string());
+    }
+
+    args
+}
+
+// Ask yt-dlp which configuration files it loads, from the debug header of a verbose run
+pub fn loaded(args: &[String], log: &LogSender) -> ConfigResult {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.arg("--verbose").args(args);
+
+    // Without a URL yt-dlp exits with an error after printing the header
+    let output = logs::run_logged(&mut cmd, "Config files", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+
+    Ok(output
+        .stderr
+        .iter()
+        .filter_map(|line| line.strip_prefix("[debug] "))
+        .filter(|line| line.contains(" config") && !line.starts_with("Command-line config"))
+        .map(|line| line.to_string())
+        .collect())
+}
@@ -0,0 +1,83 @@
+// desktop.rs
+use notify_rust::Notification;
+use std::{path::Path, process::Command, thread};
+
+// Show a desktop notification, through D-Bus freedesktop notifications on Linux.
+// Sending it can block for a while, so it happens off the UI thread
+pub fn notify(summary: &str, body: &str, success: bool, sound: bool) {
+    let mut notification = Notification::new();
+    notification
+        .appname("YT-DLP Rusty GUI")
+        .summary(summary)
+        .body(body);
+
+    if sound {
+        notification.sound_name(sound_name(success));
+    }
+
+    // A missing notification daemon shouldn't interrupt anything
+    thread::spawn(move || {
+        let _ = notification.show();
+    });
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn sound_name(success: bool) -> &'static str {
+    // Names from the freedesktop sound theme
+    if success { "complete" } else { "dialog-error" }
+}
+
+#[cfg(target_os = "macos")]
+fn sound_name(success: bool) -> &'static str {
+    if success { "Glass" } else { "Basso" }
+}
+
+#[cfg(target_os = "windows")]
+fn sound_name(success: bool) -> &'static str {
+    // Windows toast sounds
+    if success { "Default" } else { "Reminder" }
+}
+
+// Command used by yt-dlp's --exec to open the finished file with its default application
+pub fn open_file_exec() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "start \"\" {}"
+    } else if cfg!(target_os = "macos") {
+        "open {}"
+    } else {
+        "xdg-open {}"
+    }
+}
+
+// Open a file or folder with the default application
+pub fn open_path(path: &str) -> Result<(), String> {
+    let result = if cfg!(target_os = "windows") {
+        Command::new("explorer").arg(path).spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg(path).spawn()
+    } else {
+        Command::new("xdg-open").arg(path).spawn()
+    };
+
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", path, e))
+}
+
+// Open the folder containing a file, selecting the file where the file manager supports it
+pub fn show_in_folder(path: &Path) -> Result<(), String> {
+    let result = if cfg!(target_os = "windows") {
+        Command::new("explorer")
+            .arg(format!("/select,{}", path.display()))
+            .spawn()
+    } else if cfg!(target_os = "macos") {
+        Command::new("open").arg("-R").arg(path).spawn()
+    } else {
+        let folder = path.parent().unwrap_or(path);
+        Command::new("xdg-open").arg(folder).spawn()
+    };
+
+    result
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open folder of {}: {}", path.display(), e))
+}
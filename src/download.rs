@@ -0,0 +1,493 @@
+// download.rs
+use crate::album;
+use crate::config_files;
+use crate::logs::{self, LogLine, LogSender, ProcessId};
+use serde::{Deserialize, Serialize};
+use std::sync::mpsc::Sender;
+use std::{path::PathBuf, process::Command};
+
+pub const DEFAULT_TEMPLATE: &str = "%(title)s.%(ext)s";
+
+// Prefix of the lines yt-dlp prints with the final path of each finished file
+const FILEPATH_PREFIX: &str = "[ytdlprustygui:file] ";
+
+// Prefix of the progress lines, followed by the downloaded and total bytes and yt-dlp's usual line
+const PROGRESS_PREFIX: &str = "[ytdlprustygui:progress] ";
+
+pub type DownloadResult = Result<Vec<PathBuf>, String>;
+
+#[derive(Debug, Clone)]
+pub struct DownloadProgress {
+    pub downloaded: u64,
+    pub total: Option<u64>,    // Unknown for live streams
+    pub file: Option<PathBuf>, // Where the stream being downloaded ends up
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DownloadType {
+    VideoAudio,    // Combined video+audio to MP4
+    VideoOnly,     // Video stream without audio
+    Progressive,   // Single format that already contains video and audio
+    AudioOnly,     // Audio only as MP3
+    AudioOriginal, // Audio stream in its original container, no re-encoding
+    Custom,        // Raw yt-dlp -f expression typed by the user
+    MetadataOnly,  // Info JSON, description, thumbnail and links without the media
+}
+
+impl DownloadType {
+    pub const ALL: [DownloadType; 7] = [
+        DownloadType::VideoAudio,
+        DownloadType::VideoOnly,
+        DownloadType::Progressive,
+        DownloadType::AudioOnly,
+        DownloadType::AudioOriginal,
+        DownloadType::Custom,
+        DownloadType::MetadataOnly,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DownloadType::VideoAudio => "Video+Audio (MP4)",
+            DownloadType::VideoOnly => "Video Only",
+            DownloadType::Progressive => "Pre-merged",
+            DownloadType::AudioOnly => "Audio Only (MP3)",
+            DownloadType::AudioOriginal => "Audio Original",
+            DownloadType::Custom => "Custom Format",
+            DownloadType::MetadataOnly => "Metadata Only",
+        }
+    }
+
+    // Whether the user picks a format from the fetched list for this type
+    pub fn uses_video_format(&self) -> bool {
+        matches!(
+            self,
+            DownloadType::VideoAudio | DownloadType::VideoOnly | DownloadType::Progressive
+        )
+    }
+
+    pub fn uses_audio_format(&self) -> bool {
+        matches!(self, DownloadType::VideoAudio | DownloadType::AudioOriginal)
+    }
+}
+
+// Everything needed to run one yt-dlp download, independent of the UI state
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DownloadRequest {
+    pub url: String,
+    pub output_dir: String,
+    pub output_template: String,
+    pub download_type: DownloadType,
+    pub format_spec: String, // yt-dlp -f expression, empty for yt-dlp's default
+    pub archive: Option<String>, // Download archive used to skip already fetched items
+    #[serde(default)]
+    pub extra_args: Vec<String>, // Passed through to yt-dlp untouched
+    #[serde(default)]
+    pub config_location: String, // yt-dlp config file or folder, empty for the defaults
+    #[serde(default)]
+    pub ignore_config: bool, // Skip the user and system configuration files
+    #[serde(default)]
+    pub audio: AudioProcessing, // Filters applied when extracting audio
+    #[serde(default)]
+    pub live: Option<LiveOptions>, // Set when recording a live or upcoming stream
+    #[serde(default)]
+    pub album: bool, // Artist/Album folders, numbered and tagged tracks, cover and M3U
+    #[serde(default)]
+    pub metadata: MetadataOptions, // What a metadata only download writes
+}
+
+// Files written instead of the media by metadata only downloads
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MetadataOptions {
+    pub info_json: bool,
+    pub description: bool,
+    pub thumbnail: bool,
+    pub thumbnail_format: String, // jpg or png
+    pub thumbnail_width: u32,     // 0 keeps the original size
+    pub link: bool,               // .url, .webloc or .desktop shortcut depending on the OS
+}
+
+impl Default for MetadataOptions {
+    fn default() -> Self {
+        Self {
+            info_json: true,
+            description: true,
+            thumbnail: true,
+            thumbnail_format: "jpg".to_string(),
+            thumbnail_width: 0,
+            link: false,
+        }
+    }
+}
+
+impl MetadataOptions {
+    fn args(&self) -> Vec<String> {
+        let mut args = vec!["--skip-download".to_string()];
+
+        if self.info_json {
+            args.push("--write-info-json".to_string());
+        }
+        if self.description {
+            args.push("--write-description".to_string());
+        }
+        if self.thumbnail {
+            args.push("--write-thumbnail".to_string());
+            args.push("--convert-thumbnails".to_string());
+            args.push(self.thumbnail_format.clone());
+
+            // Only applies when the thumbnail is actually converted, which is the
+            // usual case since most sites serve webp
+            if self.thumbnail_width > 0 {
+                args.push("--postprocessor-args".to_string());
+                args.push(format!(
+                    "ThumbnailsConvertor:-vf scale={}:-1",
+                    self.thumbnail_width
+                ));
+            }
+        }
+        if self.link {
+            args.push("--write-link".to_string());
+        }
+
+        args
+    }
+}
+
+// How a live or upcoming stream is recorded
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LiveOptions {
+    pub from_start: bool, // Record from the beginning of the stream instead of from now
+    pub wait_for_video: bool, // Keep retrying until an upcoming stream starts
+    pub retry_interval: u64, // Seconds between retries while waiting
+}
+
+impl Default for LiveOptions {
+    fn default() -> Self {
+        Self {
+            from_start: true,
+            wait_for_video: true,
+            retry_interval: 60,
+        }
+    }
+}
+
+// Loudness normalization and silence trimming run by ffmpeg after audio extraction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AudioProcessing {
+    pub normalize: bool,
+    pub target_lufs: f32, // Integrated loudness, -16 suits podcasts, -23 is EBU R128 broadcast
+    pub true_peak: f32,   // Maximum true peak in dBTP
+    pub trim_silence: bool,
+    pub silence_threshold: f32, // dB below which the start and end count as silence
+}
+
+impl Default for AudioProcessing {
+    fn default() -> Self {
+        Self {
+            normalize: false,
+            target_lufs: -16.0,
+            true_peak: -1.5,
+            trim_silence: false,
+            silence_threshold: -50.0,
+        }
+    }
+}
+
+impl AudioProcessing {
+    // The ffmpeg -af filter chain, None when nothing is enabled
+    pub fn filter(&self) -> Option<String> {
+        let mut filters = Vec::new();
+
+        // silenceremove only trims the start, reversing the audio trims the end as well
+        if self.trim_silence {
+            let trim = format!(
+                "silenceremove=start_periods=1:start_threshold={}dB",
+                self.silence_threshold
+            );
+            filters.push(trim.clone());
+            filters.push("areverse".to_string());
+            filters.push(trim);
+            filters.push("areverse".to_string());
+        }
+
+        if self.normalize {
+            filters.push(format!(
+                "loudnorm=I={}:TP={}:LRA=11",
+                self.target_lufs, self.true_peak
+            ));
+        }
+
+        if filters.is_empty() {
+            None
+        } else {
+            Some(filters.join(","))
+        }
+    }
+}
+
+// A download waiting for the one in progress to finish
+#[derive(Debug, Clone)]
+pub struct QueuedDownload {
+    pub title: String,
+    pub request: DownloadRequest,
+}
+
+// A named set of download options that can be reused without fetching formats
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Preset {
+    pub name: String,
+    pub download_type: DownloadType,
+    pub format_spec: String,
+    #[serde(default)]
+    pub audio: AudioProcessing,
+    #[serde(default)]
+    pub config_location: String, // yt-dlp config file or folder, empty for the defaults
+    #[serde(default)]
+    pub ignore_config: bool,
+}
+
+impl Preset {
+    // A request downloading the url with this preset's options
+    pub fn request(&self, url: &str, output_dir: &str, output_template: &str) -> DownloadRequest {
+        DownloadRequest {
+            url: url.to_string(),
+            output_dir: output_dir.to_string(),
+            output_template: output_template.to_string(),
+            download_type: self.download_type.clone(),
+            format_spec: self.format_spec.clone(),
+            archive: None,
+            extra_args: Vec::new(),
+            config_location: self.config_location.trim().to_string(),
+            ignore_config: self.ignore_config,
+            audio: self.audio.clone(),
+            live: None,
+            album: false,
+            metadata: MetadataOptions::default(),
+        }
+    }
+
+    pub fn defaults() -> Vec<Preset> {
+        vec![
+            Preset {
+                name: "Best video+audio (MP4)".to_string(),
+                download_type: DownloadType::VideoAudio,
+                format_spec: "bv*+ba/b".to_string(),
+                audio: AudioProcessing::default(),
+                config_location: String::new(),
+                ignore_config: false,
+            },
+            Preset {
+                name: "Up to 1080p (MP4)".to_string(),
+                download_type: DownloadType::VideoAudio,
+                format_spec: "bv*[height<=1080]+ba/b[height<=1080]".to_string(),
+                audio: AudioProcessing::default(),
+                config_location: String::new(),
+                ignore_config: false,
+            },
+            Preset {
+                name: "Audio only (MP3)".to_string(),
+                download_type: DownloadType::AudioOnly,
+                format_spec: String::new(),
+                audio: AudioProcessing::default(),
+                config_location: String::new(),
+                ignore_config: false,
+            },
+            Preset {
+                name: "Podcast (MP3, normalized)".to_string(),
+                download_type: DownloadType::AudioOnly,
+                format_spec: String::new(),
+                audio: AudioProcessing {
+                    normalize: true,
+                    trim_silence: true,
+                    ..AudioProcessing::default()
+                },
+                config_location: String::new(),
+                ignore_config: false,
+            },
+        ]
+    }
+}
+
+impl DownloadRequest {
+    // The output template files are actually written with
+    pub fn template(&self) -> String {
+        if self.album {
+            album::track_template()
+        } else if self.output_template.is_empty() {
+            DEFAULT_TEMPLATE.to_string()
+        } else {
+            self.output_template.clone()
+        }
+    }
+
+    // The yt-dlp arguments for this request, without the shared auth/network arguments
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        if !self.format_spec.is_empty() {
+            args.push("-f".to_string());
+            args.push(self.format_spec.clone());
+        }
+
+        match self.download_type {
+            DownloadType::VideoAudio => {
+                args.push("--merge-output-format".to_string());
+                args.push("mp4".to_string());
+            }
+            DownloadType::AudioOnly => {
+                args.push("-x".to_string());
+                args.push("--audio-format".to_string());
+                args.push("mp3".to_string());
+
+                // loudnorm resamples to 192kHz internally, bring it back to what MP3 supports
+                if let Some(filter) = self.audio.filter() {
+                    args.push("--postprocessor-args".to_string());
+                    args.push(format!("ExtractAudio:-af {} -ar 48000", filter));
+                }
+            }
+            DownloadType::MetadataOnly => args.extend(self.metadata.args()),
+            // The selected stream is saved as it is
+            DownloadType::VideoOnly
+            | DownloadType::Progressive
+            | DownloadType::AudioOriginal
+            | DownloadType::Custom => {}
+        }
+
+        args.push("-o".to_string());
+        args.push(format!("{}/{}", self.output_dir, self.template()));
+
+        if self.album {
+            args.extend(album::args(&self.output_dir));
+        }
+
+        // Pick up .part files left behind by an interrupted run
+        args.push("--continue".to_string());
+
+        // Report where each file landed after merging and renaming
+        args.push("--print".to_string());
+        args.push(format!("after_move:{}%(filepath)s", FILEPATH_PREFIX));
+
+        // Machine readable progress, keeping yt-dlp's own line for the log, and the file it is
+        // written to after a tab so discarding the job knows which partial files are its own.
+        // --progress keeps the lines coming with --quiet
+        args.push("--progress".to_string());
+        args.push("--progress-template".to_string());
+        args.push(format!(
+            "download:{}%(progress.downloaded_bytes)s %(progress.total_bytes,progress.total_bytes_estimate)s %(progress._default_template)s\t%(progress.filename)s",
+            PROGRESS_PREFIX
+        ));
+
+        if let Some(live) = &self.live {
+            if live.from_start {
+                args.push("--live-from-start".to_string());
+            }
+            if live.wait_for_video {
+                args.push("--wait-for-video".to_string());
+                args.push(live.retry_interval.max(1).to_string());
+            }
+        }
+
+        if let Some(archive) = &self.archive {
+            args.push("--download-archive".to_string());
+            args.push(archive.clone());
+        }
+
+        args.extend(config_files::args(
+            &self.config_location,
+            self.ignore_config,
+        ));
+        args.extend(self.extra_args.iter().cloned());
+        args.push(self.url.clone());
+        args
+    }
+
+    pub fn command(&self, common_args: &[String]) -> Command {
+        let mut cmd = Command::new("yt-dlp");
+        cmd.args(common_args).args(self.args());
+        cmd
+    }
+}
+
+// Run the download to completion, meant to be called from a worker thread
+pub fn run(
+    request: &DownloadRequest,
+    common_args: &[String],
+    source: &str,
+    log: &LogSender,
+    process: &ProcessId,
+    progress: &Sender<DownloadProgress>,
+) -> DownloadResult {
+    let mut cmd = request.command(common_args);
+    let result = logs::run_logged_with(&mut cmd, source, log, true, Some(process), |line| {
+        if let Some(update) = parse_progress(line) {
+            let _ = progress.send(update);
+        }
+    });
+
+    match result {
+        Ok(output) if output.success => {
+            let files: Vec<PathBuf> = output
+                .stdout
+                .iter()
+                .filter_map(|line| parse_filepath(line))
+                .collect();
+
+            if request.album {
+                match album::finish_cover(&files, log) {
+                    Ok(Some(path)) => {
+                        let line = format!("Wrote cover {}", path.display());
+                        let _ = log.send(LogLine::new(source, false, &line));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let _ = log.send(LogLine::new(source, true, &e));
+                    }
+                }
+
+                match album::write_playlist(&files) {
+                    Ok(Some(path)) => {
+                        let line = format!("Wrote playlist {}", path.display());
+                        let _ = log.send(LogLine::new(source, false, &line));
+                    }
+                    Ok(None) => {}
+                    Err(e) => {
+                        let _ = log.send(LogLine::new(source, true, &e));
+                    }
+                }
+            }
+
+            Ok(files)
+        }
+        Ok(output) => Err(format!("Download failed: {}", output.error_message())),
+        Err(e) => Err(format!("Failed to execute yt-dlp: {}", e)),
+    }
+}
+
+fn parse_progress(line: &str) -> Option<DownloadProgress> {
+    let line = line.strip_prefix(PROGRESS_PREFIX)?;
+    let file = line
+        .rsplit_once('\t')
+        .map(|(_, file)| file.trim_end())
+        .filter(|file| !file.is_empty() && *file != "NA")
+        .map(PathBuf::from);
+
+    let mut fields = line.split_whitespace();
+    let downloaded = fields.next()?.parse::<f64>().ok()? as u64;
+    let total = fields
+        .next()
+        .and_then(|total| total.parse::<f64>().ok())
+        .map(|total| total as u64);
+
+    Some(DownloadProgress {
+        downloaded,
+        total,
+        file,
+    })
+}
+
+pub fn parse_filepath(line: &str) -> Option<PathBuf> {
+    line.strip_prefix(FILEPATH_PREFIX)
+        .map(|path| PathBuf::from(path.trim_end()))
+}
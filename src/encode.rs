@@ -0,0 +1,157 @@
+// encode.rs
+use crate::logs::{self, LogSender};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::Sender;
+
+pub enum EncodeEvent {
+    Progress(f32), // 0.0 to 1.0 of the current file
+    Finished(Result<Vec<PathBuf>, String>),
+}
+
+// A named ffmpeg conversion run on finished downloads
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EncodeProfile {
+    pub name: String,
+    pub extension: String,
+    pub args: String, // ffmpeg output options, quoted like in a shell
+}
+
+impl EncodeProfile {
+    pub fn defaults() -> Vec<EncodeProfile> {
+        let profile = |name: &str, extension: &str, args: &str| EncodeProfile {
+            name: name.to_string(),
+            extension: extension.to_string(),
+            args: args.to_string(),
+        };
+
+        vec![
+            profile(
+                "H.264 720p for sharing",
+                "mp4",
+                "-c:v libx264 -crf 23 -preset medium -vf scale=-2:720 -c:a aac -b:a 128k -movflags +faststart",
+            ),
+            profile(
+                "H.265 archival",
+                "mkv",
+                "-c:v libx265 -crf 20 -preset slow -c:a copy",
+            ),
+            profile(
+                "ProRes 422 HQ for editing",
+                "mov",
+                "-c:v prores_ks -profile:v 3 -pix_fmt yuv422p10le -c:a pcm_s16le",
+            ),
+            profile(
+                "Animated GIF",
+                "gif",
+                "-vf \"fps=12,scale=480:-1:flags=lanczos,split[a][b];[a]palettegen[p];[b][p]paletteuse\" -loop 0",
+            ),
+            profile(
+                "WAV 48kHz for editing",
+                "wav",
+                "-vn -c:a pcm_s16le -ar 48000",
+            ),
+        ]
+    }
+}
+
+// Encode each file in turn, sending progress while ffmpeg runs
+pub fn run(
+    files: &[PathBuf],
+    profile: &EncodeProfile,
+    keep_original: bool,
+    sender: &Sender<EncodeEvent>,
+    log: &LogSender,
+) {
+    let mut outputs = Vec::new();
+
+    for file in files {
+        match encode_file(file, profile, keep_original, sender, log) {
+            Ok(output) => outputs.push(output),
+            Err(e) => {
+                let _ = sender.send(EncodeEvent::Finished(Err(e)));
+                return;
+            }
+        }
+    }
+
+    let _ = sender.send(EncodeEvent::Finished(Ok(outputs)));
+}
+
+fn encode_file(
+    input: &Path,
+    profile: &EncodeProfile,
+    keep_original: bool,
+    sender: &Sender<EncodeEvent>,
+    log: &LogSender,
+) -> Result<PathBuf, String> {
+    let args = shlex::split(&profile.args)
+        .ok_or_else(|| format!("Encode profile '{}' has unbalanced quotes", profile.name))?;
+
+    // Never write over the input while ffmpeg is still reading it
+    let mut output = input.with_extension(&profile.extension);
+    let replaces_input = output == input;
+    if replaces_input {
+        let stem = input.file_stem().unwrap_or_default().to_string_lossy();
+        output = input.with_file_name(format!("{} (encoded).{}", stem, profile.extension));
+    }
+
+    let duration = probe_duration(input);
+    let _ = sender.send(EncodeEvent::Progress(0.0));
+
+    let mut cmd = Command::new("ffmpeg");
+    cmd.args(["-hide_banner", "-nostdin", "-nostats", "-y", "-i"])
+        .arg(input)
+        .args(&args)
+        .args(["-progress", "pipe:1"])
+        .arg(&output);
+
+    // -progress writes key=value lines, out_time_us is the position in the output
+    let result = logs::run_logged_with(&mut cmd, "Encode", log, false, None, |line| {
+        if let (Some(value), Some(duration)) = (line.strip_prefix("out_time_us="), duration)
+            && let Ok(micros) = value.trim().parse::<f64>()
+        {
+            let progress = (micros / 1_000_000.0 / duration).clamp(0.0, 1.0);
+            let _ = sender.send(EncodeEvent::Progress(progress as f32));
+        }
+    })
+    .map_err(|e| format!("Failed to execute ffmpeg: {}", e))?;
+
+    if !result.success {
+        let _ = fs::remove_file(&output);
+        let error = result.stderr.last().cloned().unwrap_or_default();
+        return Err(format!("Encoding failed: {}", error));
+    }
+
+    if !keep_original {
+        fs::remove_file(input).map_err(|e| format!("Failed to remove original: {}", e))?;
+
+        if replaces_input {
+            fs::rename(&output, input)
+                .map_err(|e| format!("Failed to rename encoded file: {}", e))?;
+            return Ok(input.to_path_buf());
+        }
+    }
+
+    Ok(output)
+}
+
+// Length of a media file in seconds, used to turn ffmpeg's position into a percentage
+pub fn probe_duration(path: &Path) -> Option<f64> {
+    let output = Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-show_entries",
+            "format=duration",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .ok()?;
+
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
@@ -0,0 +1,79 @@
+// export.rs
+use crate::channel::{self, ChannelEntry};
+use crate::logs::{self, LogSender};
+use std::path::Path;
+use std::{fs, process::Command};
+
+pub type ExportResult = Result<usize, String>;
+
+// List a playlist (or a single video) and write it to a CSV file, returns the number of rows
+pub fn playlist_csv(
+    url: &str,
+    exact: bool,
+    path: &Path,
+    common_args: &[String],
+    log: &LogSender,
+) -> ExportResult {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args).arg("-J");
+
+    // Flat listing is one request per page, exact dates need every video's page
+    if !exact {
+        cmd.args([
+            "--flat-playlist",
+            "--extractor-args",
+            "youtubetab:approximate_date",
+        ]);
+    }
+    cmd.arg(url);
+
+    let output = logs::run_logged(&mut cmd, "Export", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!("Export failed: {}", output.error_message()));
+    }
+
+    let entries = channel::parse_entries(&output.stdout.join("\n"), 1)?;
+    fs::write(path, to_csv(&entries)).map_err(|e| format!("Failed to write CSV: {}", e))?;
+    Ok(entries.len())
+}
+
+fn to_csv(entries: &[ChannelEntry]) -> String {
+    let mut csv = String::from("index,title,url,duration,upload_date,view_count\n");
+
+    for entry in entries {
+        let fields = [
+            entry.index.to_string(),
+            entry.title.clone(),
+            entry.url.clone(),
+            entry
+                .duration
+                .map(|seconds| format!("{:.0}", seconds))
+                .unwrap_or_default(),
+            entry
+                .upload_date
+                .as_deref()
+                .map(channel::format_date)
+                .unwrap_or_default(),
+            entry
+                .view_count
+                .map(|views| views.to_string())
+                .unwrap_or_default(),
+        ];
+
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+// Quote a field when it contains a separator, quote or line break (RFC 4180)
+pub fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
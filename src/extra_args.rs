@@ -0,0 +1,544 @@
+// extra_args.rs
+use serde::{Deserialize, Serialize};
+
+// Flags the GUI sets itself, passing them again usually conflicts with the GUI's choice
+const GUI_FLAGS: &[&str] = &[
+    "-f",
+    "--format",
+    "-o",
+    "--output",
+    "--merge-output-format",
+    "-x",
+    "--extract-audio",
+    "--audio-format",
+    "--download-archive",
+    "-c",
+    "--continue",
+    "--no-continue",
+    "-O",
+    "--print",
+    "--exec",
+    "--cookies",
+    "--cookies-from-browser",
+    "-u",
+    "--username",
+    "-p",
+    "--password",
+    "-n",
+    "--netrc",
+    "--netrc-location",
+    "--proxy",
+    "-r",
+    "--limit-rate",
+    "-R",
+    "--retries",
+    "--fragment-retries",
+    "--socket-timeout",
+    "-N",
+    "--concurrent-fragments",
+    "--source-address",
+    "-4",
+    "--force-ipv4",
+    "-6",
+    "--force-ipv6",
+    "--sleep-requests",
+    "--sleep-interval",
+    "--min-sleep-interval",
+    "--max-sleep-interval",
+    "-q",
+    "--quiet",
+    "-v",
+    "--verbose",
+    "--ignore-config",
+    "--no-config-locations",
+    "--config-locations",
+    "--progress-template",
+];
+
+// Other yt-dlp options, anything not in either list gets an "unknown flag" warning
+const KNOWN_FLAGS: &[&str] = &[
+    "-h",
+    "--help",
+    "--version",
+    "-U",
+    "--update",
+    "--no-update",
+    "--update-to",
+    "-i",
+    "--ignore-errors",
+    "--no-abort-on-error",
+    "--abort-on-error",
+    "--dump-user-agent",
+    "--list-extractors",
+    "--extractor-descriptions",
+    "--preset-alias",
+    "-t",
+    "--no-plugin-dirs",
+    "--use-extractors",
+    "--default-search",
+    "--plugin-dirs",
+    "--flat-playlist",
+    "--no-flat-playlist",
+    "--live-from-start",
+    "--no-live-from-start",
+    "--wait-for-video",
+    "--no-wait-for-video",
+    "--mark-watched",
+    "--no-mark-watched",
+    "--color",
+    "--compat-options",
+    "--alias",
+    "--impersonate",
+    "--list-impersonate-targets",
+    "--enable-file-urls",
+    "--geo-bypass",
+    "--no-geo-bypass",
+    "--geo-bypass-country",
+    "--geo-bypass-ip-block",
+    "--geo-verification-proxy",
+    "--xff",
+    "-I",
+    "--playlist-items",
+    "--min-filesize",
+    "--max-filesize",
+    "--date",
+    "--datebefore",
+    "--dateafter",
+    "--match-filters",
+    "--no-match-filters",
+    "--match-filter",
+    "--match-title",
+    "--reject-title",
+    "--break-match-filters",
+    "--no-playlist",
+    "--yes-playlist",
+    "--playlist-start",
+    "--playlist-end",
+    "--playlist-reverse",
+    "--no-playlist-reverse",
+    "--age-limit",
+    "--no-download-archive",
+    "--max-downloads",
+    "--break-on-existing",
+    "--no-break-on-existing",
+    "--break-per-input",
+    "--no-break-per-input",
+    "--skip-playlist-after-errors",
+    "--throttled-rate",
+    "--file-access-retries",
+    "--retry-sleep",
+    "--skip-unavailable-fragments",
+    "--abort-on-unavailable-fragments",
+    "--no-skip-unavailable-fragments",
+    "--keep-fragments",
+    "--no-keep-fragments",
+    "--buffer-size",
+    "--resize-buffer",
+    "--no-resize-buffer",
+    "--http-chunk-size",
+    "--playlist-random",
+    "--lazy-playlist",
+    "--no-lazy-playlist",
+    "--xattr-set-filesize",
+    "--hls-use-mpegts",
+    "--no-hls-use-mpegts",
+    "--hls-prefer-native",
+    "--hls-prefer-ffmpeg",
+    "--hls-split-discontinuity",
+    "--no-hls-split-discontinuity",
+    "--download-sections",
+    "--downloader",
+    "--external-downloader",
+    "--downloader-args",
+    "--external-downloader-args",
+    "-a",
+    "--batch-file",
+    "--no-batch-file",
+    "-P",
+    "--paths",
+    "--output-na-placeholder",
+    "--restrict-filenames",
+    "--no-restrict-filenames",
+    "--windows-filenames",
+    "--no-windows-filenames",
+    "--trim-filenames",
+    "--autonumber-start",
+    "-w",
+    "--no-overwrites",
+    "--force-overwrites",
+    "--no-force-overwrites",
+    "--part",
+    "--no-part",
+    "--mtime",
+    "--no-mtime",
+    "--write-description",
+    "--no-write-description",
+    "--write-info-json",
+    "--no-write-info-json",
+    "--write-playlist-metafiles",
+    "--no-write-playlist-metafiles",
+    "--clean-info-json",
+    "--no-clean-info-json",
+    "--write-comments",
+    "--no-write-comments",
+    "--load-info-json",
+    "--no-cookies",
+    "--no-cookies-from-browser",
+    "--cache-dir",
+    "--no-cache-dir",
+    "--rm-cache-dir",
+    "--write-thumbnail",
+    "--no-write-thumbnail",
+    "--write-all-thumbnails",
+    "--list-thumbnails",
+    "--write-link",
+    "--write-url-link",
+    "--write-webloc-link",
+    "--write-desktop-link",
+    "-s",
+    "--simulate",
+    "--no-simulate",
+    "--ignore-no-formats-error",
+    "--no-ignore-no-formats-error",
+    "--skip-download",
+    "--print-to-file",
+    "-j",
+    "--dump-json",
+    "-J",
+    "--dump-single-json",
+    "--force-write-archive",
+    "--newline",
+    "--no-quiet",
+    "--no-warnings",
+    "-g",
+    "--get-url",
+    "-e",
+    "--get-title",
+    "--get-id",
+    "--get-thumbnail",
+    "--get-description",
+    "--get-duration",
+    "--get-filename",
+    "--get-format",
+    "--no-progress",
+    "--progress",
+    "--console-title",
+    "--progress-delta",
+    "--dump-pages",
+    "--write-pages",
+    "--print-traffic",
+    "--encoding",
+    "--legacy-server-connect",
+    "--no-check-certificates",
+    "--prefer-insecure",
+    "--add-headers",
+    "--referer",
+    "--user-agent",
+    "--bidi-workaround",
+    "--sleep-subtitles",
+    "-S",
+    "--format-sort",
+    "--format-sort-force",
+    "--no-format-sort-force",
+    "--video-multistreams",
+    "--no-video-multistreams",
+    "--audio-multistreams",
+    "--no-audio-multistreams",
+    "--prefer-free-formats",
+    "--no-prefer-free-formats",
+    "--check-formats",
+    "--check-all-formats",
+    "--no-check-formats",
+    "-F",
+    "--list-formats",
+    "--write-subs",
+    "--no-write-subs",
+    "--write-auto-subs",
+    "--no-write-auto-subs",
+    "--list-subs",
+    "--sub-format",
+    "--sub-langs",
+    "--twofactor",
+    "-2",
+    "--netrc-cmd",
+    "--client-certificate",
+    "--client-certificate-key",
+    "--client-certificate-password",
+    "--ap-list-mso",
+    "--video-password",
+    "--ap-mso",
+    "--ap-username",
+    "--ap-password",
+    "--audio-quality",
+    "--remux-video",
+    "--recode-video",
+    "--postprocessor-args",
+    "--ppa",
+    "-k",
+    "--keep-video",
+    "--no-keep-video",
+    "--post-overwrites",
+    "--no-post-overwrites",
+    "--embed-subs",
+    "--no-embed-subs",
+    "--embed-thumbnail",
+    "--no-embed-thumbnail",
+    "--embed-metadata",
+    "--no-embed-metadata",
+    "--add-metadata",
+    "--no-add-metadata",
+    "--embed-chapters",
+    "--no-embed-chapters",
+    "--add-chapters",
+    "--no-add-chapters",
+    "--embed-info-json",
+    "--no-embed-info-json",
+    "--parse-metadata",
+    "--replace-in-metadata",
+    "--xattrs",
+    "--concat-playlist",
+    "--fixup",
+    "--ffmpeg-location",
+    "--no-exec",
+    "--convert-subs",
+    "--convert-thumbnails",
+    "--split-chapters",
+    "--no-split-chapters",
+    "--remove-chapters",
+    "--no-remove-chapters",
+    "--force-keyframes-at-cuts",
+    "--no-force-keyframes-at-cuts",
+    "--use-postprocessor",
+    "--sponsorblock-mark",
+    "--sponsorblock-remove",
+    "--sponsorblock-chapter-title",
+    "--no-sponsorblock",
+    "--sponsorblock-api",
+    "--extractor-retries",
+    "--allow-dynamic-mpd",
+    "--ignore-dynamic-mpd",
+    "--no-allow-dynamic-mpd",
+    "--extractor-args",
+];
+
+// Options followed by a value, which must not be checked as an option itself
+const TAKES_VALUE: &[&str] = &[
+    "-f",
+    "--format",
+    "-o",
+    "--output",
+    "--merge-output-format",
+    "--audio-format",
+    "--download-archive",
+    "-O",
+    "--print",
+    "--exec",
+    "--cookies",
+    "--cookies-from-browser",
+    "-u",
+    "--username",
+    "-p",
+    "--password",
+    "--netrc-location",
+    "--proxy",
+    "-r",
+    "--limit-rate",
+    "-R",
+    "--retries",
+    "--fragment-retries",
+    "--socket-timeout",
+    "-N",
+    "--concurrent-fragments",
+    "--source-address",
+    "--sleep-requests",
+    "--sleep-interval",
+    "--min-sleep-interval",
+    "--max-sleep-interval",
+    "--config-locations",
+    "--progress-template",
+    "--update-to",
+    "--use-extractors",
+    "--default-search",
+    "--plugin-dirs",
+    "--preset-alias",
+    "-t",
+    "--wait-for-video",
+    "--color",
+    "--compat-options",
+    "--alias",
+    "--impersonate",
+    "--geo-verification-proxy",
+    "--xff",
+    "--geo-bypass-country",
+    "--geo-bypass-ip-block",
+    "-I",
+    "--playlist-items",
+    "--playlist-start",
+    "--playlist-end",
+    "--min-filesize",
+    "--max-filesize",
+    "--date",
+    "--datebefore",
+    "--dateafter",
+    "--match-filters",
+    "--match-filter",
+    "--break-match-filters",
+    "--match-title",
+    "--reject-title",
+    "--age-limit",
+    "--max-downloads",
+    "--skip-playlist-after-errors",
+    "--throttled-rate",
+    "--file-access-retries",
+    "--retry-sleep",
+    "--buffer-size",
+    "--http-chunk-size",
+    "--download-sections",
+    "--downloader",
+    "--external-downloader",
+    "--downloader-args",
+    "--external-downloader-args",
+    "-a",
+    "--batch-file",
+    "-P",
+    "--paths",
+    "--output-na-placeholder",
+    "--trim-filenames",
+    "--autonumber-start",
+    "--load-info-json",
+    "--cache-dir",
+    "--print-to-file",
+    "--progress-delta",
+    "--encoding",
+    "--add-headers",
+    "--referer",
+    "--user-agent",
+    "--sleep-subtitles",
+    "-S",
+    "--format-sort",
+    "--sub-format",
+    "--sub-langs",
+    "-2",
+    "--twofactor",
+    "--netrc-cmd",
+    "--client-certificate",
+    "--client-certificate-key",
+    "--client-certificate-password",
+    "--video-password",
+    "--ap-mso",
+    "--ap-username",
+    "--ap-password",
+    "--audio-quality",
+    "--remux-video",
+    "--recode-video",
+    "--postprocessor-args",
+    "--ppa",
+    "--parse-metadata",
+    "--replace-in-metadata",
+    "--fixup",
+    "--ffmpeg-location",
+    "--convert-subs",
+    "--convert-thumbnails",
+    "--remove-chapters",
+    "--use-postprocessor",
+    "--sponsorblock-mark",
+    "--sponsorblock-remove",
+    "--sponsorblock-chapter-title",
+    "--sponsorblock-api",
+    "--extractor-retries",
+    "--extractor-args",
+];
+
+// A named set of extra arguments the user can reuse
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentPreset {
+    pub name: String,
+    pub args: String,
+    #[serde(default)]
+    pub config_location: String,
+    #[serde(default)]
+    pub ignore_config: bool,
+}
+
+// Split the text like a POSIX shell would, so quoted values stay together
+pub fn parse(text: &str) -> Result<Vec<String>, String> {
+    shlex::split(text).ok_or_else(|| "Extra arguments have unbalanced quotes".to_string())
+}
+
+// Warnings about flags yt-dlp doesn't know or that the GUI already sets
+pub fn warnings(args: &[String]) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    let mut values = 0;
+    for arg in args {
+        // The value of the previous option, even when it starts with "-"
+        if values > 0 {
+            values -= 1;
+            continue;
+        }
+        if !arg.starts_with('-') || arg == "-" || arg.parse::<f64>().is_ok() {
+            continue;
+        }
+
+        // Values can also be attached, "--flag=value" or "-fvalue"
+        let (flag, attached) = if arg.starts_with("--") {
+            match arg.split_once('=') {
+                Some((flag, _)) => (flag, true),
+                None => (arg.as_str(), false),
+            }
+        } else {
+            match arg.char_indices().nth(2) {
+                Some((index, _)) => (&arg[..index], true),
+                None => (arg.as_str(), false),
+            }
+        };
+
+        if GUI_FLAGS.contains(&flag) {
+            warnings.push(format!("{} is already set by the GUI", flag));
+        } else if !KNOWN_FLAGS.contains(&flag) {
+            warnings.push(format!("{} is not a known yt-dlp option", flag));
+        }
+
+        if !attached {
+            values = value_count(flag);
+        }
+    }
+
+    warnings
+}
+
+// How many values follow an option given on its own
+fn value_count(flag: &str) -> usize {
+    match flag {
+        "--print-to-file" => 2,
+        "--replace-in-metadata" => 3,
+        _ if TAKES_VALUE.contains(&flag) => 1,
+        _ => 0,
+    }
+}
+
+// Quote the arguments so the command line can be pasted into a shell
+pub fn command_line(args: &[String]) -> String {
+    let mut masked = Vec::with_capacity(args.len());
+    let mut hide_next = false;
+
+    for arg in args {
+        if hide_next {
+            masked.push("********".to_string());
+        } else {
+            masked.push(arg.clone());
+        }
+        hide_next = arg == "--password" || arg == "-p";
+    }
+
+    let quoted: Vec<String> = masked
+        .iter()
+        .map(|arg| {
+            shlex::try_quote(arg)
+                .map(|quoted| quoted.to_string())
+                .unwrap_or_else(|_| arg.clone())
+        })
+        .collect();
+
+    format!("yt-dlp {}", quoted.join(" "))
+}
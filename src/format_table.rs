@@ -0,0 +1,178 @@
+// format_table.rs
+use crate::formats::{Format, format_size};
+use eframe::egui::{self, RichText, Sense};
+use egui_extras::{Column, TableBuilder};
+use std::cmp::Ordering;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SortColumn {
+    Id,
+    Container,
+    Resolution,
+    Fps,
+    VideoCodec,
+    AudioCodec,
+    Bitrate,
+    Filesize,
+    Hdr,
+    Language,
+}
+
+const COLUMNS: [(SortColumn, &str); 10] = [
+    (SortColumn::Id, "ID"),
+    (SortColumn::Container, "Container"),
+    (SortColumn::Resolution, "Resolution"),
+    (SortColumn::Fps, "FPS"),
+    (SortColumn::VideoCodec, "VCodec"),
+    (SortColumn::AudioCodec, "ACodec"),
+    (SortColumn::Bitrate, "Bitrate"),
+    (SortColumn::Filesize, "Filesize"),
+    (SortColumn::Hdr, "HDR"),
+    (SortColumn::Language, "Language"),
+];
+
+// Sort and filter state of one format table
+pub struct FormatTable {
+    id: &'static str,
+    sort: Option<SortColumn>,
+    ascending: bool,
+    codec_filter: String,
+    resolution_filter: String,
+}
+
+impl FormatTable {
+    pub fn new(id: &'static str) -> Self {
+        Self {
+            id,
+            sort: None,
+            ascending: true,
+            codec_filter: String::new(),
+            resolution_filter: String::new(),
+        }
+    }
+
+    // Show the formats, clicking a row selects it
+    pub fn show(&mut self, ui: &mut egui::Ui, formats: &[Format], selected: &mut String) {
+        ui.horizontal(|ui| {
+            ui.label("Codec:");
+            ui.add(egui::TextEdit::singleline(&mut self.codec_filter).desired_width(80.0));
+            ui.label("Resolution:");
+            ui.add(egui::TextEdit::singleline(&mut self.resolution_filter).desired_width(80.0));
+        });
+
+        let codec = self.codec_filter.to_lowercase();
+        let resolution = self.resolution_filter.to_lowercase();
+        let mut rows: Vec<&Format> = formats
+            .iter()
+            .filter(|f| {
+                codec.is_empty()
+                    || f.vcodec.to_lowercase().contains(&codec)
+                    || f.acodec.to_lowercase().contains(&codec)
+            })
+            .filter(|f| resolution.is_empty() || f.resolution.to_lowercase().contains(&resolution))
+            .collect();
+
+        if let Some(column) = self.sort {
+            rows.sort_by(|a, b| compare(column, a, b));
+            if !self.ascending {
+                rows.reverse();
+            }
+        }
+
+        ui.push_id(self.id, |ui| {
+            TableBuilder::new(ui)
+                .striped(true)
+                .sense(Sense::click())
+                .max_scroll_height(300.0)
+                .columns(Column::auto().resizable(true), COLUMNS.len() - 1)
+                .column(Column::remainder())
+                .header(20.0, |mut header| {
+                    for (column, name) in COLUMNS {
+                        header.col(|ui| {
+                            let arrow = match (self.sort == Some(column), self.ascending) {
+                                (true, true) => " ⏶",
+                                (true, false) => " ⏷",
+                                _ => "",
+                            };
+                            let label = RichText::new(format!("{}{}", name, arrow)).strong();
+
+                            if ui.button(label).clicked() {
+                                if self.sort == Some(column) {
+                                    self.ascending = !self.ascending;
+                                } else {
+                                    self.sort = Some(column);
+                                    self.ascending = true;
+                                }
+                            }
+                        });
+                    }
+                })
+                .body(|mut body| {
+                    for format in rows {
+                        body.row(18.0, |mut row| {
+                            row.set_selected(*selected == format.id);
+
+                            row.col(|ui| {
+                                ui.label(&format.id);
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.extension);
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.resolution);
+                            });
+                            row.col(|ui| {
+                                ui.label(format.fps.map(|fps| fps.to_string()).unwrap_or_default());
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.vcodec);
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.acodec);
+                            });
+                            row.col(|ui| {
+                                ui.label(
+                                    format
+                                        .bitrate
+                                        .map(|tbr| format!("{:.0}k", tbr))
+                                        .unwrap_or_default(),
+                                );
+                            });
+                            row.col(|ui| {
+                                ui.label(format.filesize.map(format_size).unwrap_or_default());
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.dynamic_range);
+                            });
+                            row.col(|ui| {
+                                ui.label(&format.language);
+                            });
+
+                            if row.response().clicked() {
+                                *selected = format.id.clone();
+                            }
+                        });
+                    }
+                });
+        });
+    }
+}
+
+fn compare(column: SortColumn, a: &Format, b: &Format) -> Ordering {
+    match column {
+        SortColumn::Id => a.id.cmp(&b.id),
+        SortColumn::Container => a.extension.cmp(&b.extension),
+        SortColumn::Resolution => a.height.cmp(&b.height),
+        SortColumn::Fps => compare_numbers(a.fps, b.fps),
+        SortColumn::VideoCodec => a.vcodec.cmp(&b.vcodec),
+        SortColumn::AudioCodec => a.acodec.cmp(&b.acodec),
+        SortColumn::Bitrate => compare_numbers(a.bitrate, b.bitrate),
+        SortColumn::Filesize => a.filesize.cmp(&b.filesize),
+        SortColumn::Hdr => a.dynamic_range.cmp(&b.dynamic_range),
+        SortColumn::Language => a.language.cmp(&b.language),
+    }
+}
+
+fn compare_numbers(a: Option<f64>, b: Option<f64>) -> Ordering {
+    a.unwrap_or(0.0).total_cmp(&b.unwrap_or(0.0))
+}
@@ -0,0 +1,243 @@
+// formats.rs
+use serde_json::Value;
+
+#[derive(Debug, Clone)]
+pub struct Format {
+    pub id: String,
+    pub extension: String,
+    pub resolution: String,
+    pub description: String,
+    pub is_video: bool,        // Flag to indicate if this is a video format
+    pub is_audio: bool,        // Flag to indicate if this is an audio format
+    pub filesize: Option<u64>, // Exact or approximate size reported by the site
+    pub bitrate: Option<f64>,  // Total bitrate in kbit/s
+    pub height: Option<u64>,
+    pub fps: Option<f64>,
+    pub vcodec: String,
+    pub acodec: String,
+    pub dynamic_range: String, // SDR, HDR10, HLG...
+    pub language: String,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum LiveStatus {
+    #[default]
+    NotLive, // Regular video, or a stream that has ended
+    Live,     // Streaming right now
+    Upcoming, // Scheduled stream or premiere
+}
+
+// What we know about a video after fetching its metadata
+#[derive(Debug, Clone, Default)]
+pub struct VideoInfo {
+    pub video_formats: Vec<Format>,
+    pub audio_formats: Vec<Format>,
+    pub duration: Option<f64>, // Seconds
+    pub live_status: LiveStatus,
+    pub release_timestamp: Option<i64>, // When an upcoming stream is scheduled to start
+    pub subtitles: Vec<SubtitleTrack>,
+    pub live_chat: bool, // A live chat replay can be downloaded
+}
+
+// A subtitle language offered by the site
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleTrack {
+    pub lang: String, // yt-dlp's language key, e.g. en or en-orig
+    pub name: String,
+    pub automatic: bool, // Auto-generated captions rather than uploaded subtitles
+}
+
+impl SubtitleTrack {
+    pub fn label(&self) -> String {
+        let name = if self.name.is_empty() {
+            &self.lang
+        } else {
+            &self.name
+        };
+        if self.automatic {
+            format!("{} (auto-generated)", name)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
+impl Format {
+    // Reported size, or bitrate × duration when the site doesn't report one
+    pub fn estimated_size(&self, duration: Option<f64>) -> Option<u64> {
+        self.filesize.or_else(|| {
+            let bytes = self.bitrate? * 1000.0 / 8.0 * duration?;
+            Some(bytes as u64)
+        })
+    }
+}
+
+// Parse the output of `yt-dlp -J`, for playlists the first entry is used
+pub fn parse_info_json(output: &str) -> Result<VideoInfo, String> {
+    let json: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))?;
+
+    let info = match json.get("entries").and_then(Value::as_array) {
+        Some(entries) => entries.first().ok_or("The playlist has no entries")?,
+        None => &json,
+    };
+
+    let formats: Vec<Format> = info
+        .get("formats")
+        .and_then(Value::as_array)
+        .map(|formats| formats.iter().filter_map(parse_format).collect())
+        .unwrap_or_default();
+
+    // Separate video and audio formats
+    Ok(VideoInfo {
+        video_formats: formats.iter().filter(|f| f.is_video).cloned().collect(),
+        audio_formats: formats.iter().filter(|f| f.is_audio).cloned().collect(),
+        duration: info.get("duration").and_then(Value::as_f64),
+        live_status: match info.get("live_status").and_then(Value::as_str) {
+            Some("is_live") => LiveStatus::Live,
+            Some("is_upcoming") => LiveStatus::Upcoming,
+            _ => LiveStatus::NotLive,
+        },
+        release_timestamp: info.get("release_timestamp").and_then(Value::as_i64),
+        subtitles: parse_subtitles(info, "subtitles", false)
+            .into_iter()
+            .chain(parse_subtitles(info, "automatic_captions", true))
+            .collect(),
+        live_chat: info
+            .get("subtitles")
+            .and_then(|subtitles| subtitles.get("live_chat"))
+            .is_some(),
+    })
+}
+
+fn parse_subtitles(info: &Value, key: &str, automatic: bool) -> Vec<SubtitleTrack> {
+    let Some(tracks) = info.get(key).and_then(Value::as_object) else {
+        return Vec::new();
+    };
+
+    let mut tracks: Vec<SubtitleTrack> = tracks
+        .iter()
+        // The chat replay is exported on its own, it isn't a subtitle
+        .filter(|(lang, _)| lang.as_str() != "live_chat")
+        .map(|(lang, formats)| SubtitleTrack {
+            lang: lang.clone(),
+            name: formats
+                .get(0)
+                .and_then(|format| format.get("name"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string(),
+            automatic,
+        })
+        .collect();
+
+    // YouTube offers the original language of auto captions as -orig, list it first
+    tracks.sort_by_key(|track| (!track.lang.ends_with("-orig"), track.lang.clone()));
+    tracks
+}
+
+fn parse_format(format: &Value) -> Option<Format> {
+    let text = |key: &str| format.get(key).and_then(Value::as_str).unwrap_or("");
+
+    let id = text("format_id").to_string();
+    if id.is_empty() {
+        return None;
+    }
+
+    let vcodec = text("vcodec");
+    let acodec = text("acodec");
+    let has_video = !vcodec.is_empty() && vcodec != "none";
+    let has_audio = !acodec.is_empty() && acodec != "none";
+
+    let filesize = format
+        .get("filesize")
+        .and_then(Value::as_u64)
+        .or_else(|| format.get("filesize_approx").and_then(Value::as_u64));
+    let bitrate = format.get("tbr").and_then(Value::as_f64);
+    let fps = format.get("fps").and_then(Value::as_f64);
+
+    // Build a description similar to the --list-formats columns
+    let mut details = Vec::new();
+    if !text("format_note").is_empty() {
+        details.push(text("format_note").to_string());
+    }
+    if let Some(fps) = fps {
+        details.push(format!("{}fps", fps));
+    }
+    if has_video {
+        details.push(vcodec.to_string());
+    }
+    if has_audio {
+        details.push(acodec.to_string());
+    }
+    if let Some(bitrate) = bitrate {
+        details.push(format!("{:.0}k", bitrate));
+    }
+    if let Some(size) = filesize {
+        details.push(format_size(size));
+    }
+
+    let resolution = if has_video {
+        text("resolution").to_string()
+    } else {
+        "audio only".to_string()
+    };
+
+    Some(Format {
+        id,
+        extension: text("ext").to_string(),
+        resolution,
+        description: details.join(", "),
+        is_video: has_video,
+        is_audio: has_audio && !has_video,
+        filesize,
+        bitrate,
+        height: format.get("height").and_then(Value::as_u64),
+        fps,
+        vcodec: if has_video {
+            vcodec.to_string()
+        } else {
+            String::new()
+        },
+        acodec: if has_audio {
+            acodec.to_string()
+        } else {
+            String::new()
+        },
+        dynamic_range: if has_video {
+            text("dynamic_range").to_string()
+        } else {
+            String::new()
+        },
+        language: text("language").to_string(),
+    })
+}
+
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+// Seconds as h:mm:ss, or m:ss for anything shorter than an hour
+pub fn format_duration(seconds: f64) -> String {
+    let total = seconds.max(0.0).round() as u64;
+    let (hours, minutes, seconds) = (total / 3600, total / 60 % 60, total % 60);
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}
@@ -0,0 +1,56 @@
+// history.rs
+use serde::{Deserialize, Serialize};
+use std::{fs, path::PathBuf};
+
+// Older entries are dropped from the history past this
+const MAX_ENTRIES: usize = 1000;
+
+// A finished, failed or stopped download
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub files: Vec<PathBuf>,
+    pub finished: i64, // Unix timestamp
+    pub success: bool,
+    pub message: String,
+}
+
+impl HistoryEntry {
+    // Name of the first file, the URL when nothing was saved
+    pub fn title(&self) -> String {
+        self.files
+            .first()
+            .and_then(|file| file.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.url.clone())
+    }
+}
+
+fn history_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ytdlprustygui").join("history.json"))
+}
+
+pub fn load() -> Vec<HistoryEntry> {
+    history_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(entries: &mut Vec<HistoryEntry>) -> Result<(), String> {
+    if entries.len() > MAX_ENTRIES {
+        let excess = entries.len() - MAX_ENTRIES;
+        entries.drain(..excess);
+    }
+
+    let path = history_path().ok_or("Could not determine the config directory")?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let contents = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize history: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to save history: {}", e))
+}
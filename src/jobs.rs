@@ -0,0 +1,74 @@
+// jobs.rs
+use crate::download::DownloadRequest;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path, path::PathBuf};
+
+// Suffixes of the temporary files yt-dlp leaves behind while downloading
+const PARTIAL_SUFFIXES: [&str; 3] = [".part", ".ytdl", ".temp"];
+
+// A download that has been started, kept on disk until it finishes
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u64,
+    pub request: DownloadRequest,
+    pub started: i64, // Unix timestamp
+    #[serde(default)]
+    pub files: Vec<PathBuf>, // Files yt-dlp reported writing to, for discarding the job
+}
+
+fn jobs_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ytdlprustygui").join("jobs.json"))
+}
+
+// Jobs still on disk at startup were interrupted by a crash or by closing the app
+pub fn load() -> Vec<Job> {
+    jobs_path()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(jobs: &[Job]) -> Result<(), String> {
+    let path = jobs_path().ok_or("Could not determine the config directory")?;
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config directory: {}", e))?;
+    }
+
+    let contents = serde_json::to_string_pretty(jobs)
+        .map_err(|e| format!("Failed to serialize jobs: {}", e))?;
+    fs::write(&path, contents).map_err(|e| format!("Failed to save jobs: {}", e))
+}
+
+// Delete the files a job was writing and the partial files next to them, returns how many
+// were removed. Only the job's own files are touched, other programs download to the same folders
+pub fn remove_partial_files(job: &Job) -> usize {
+    job.files
+        .iter()
+        .map(|file| remove_file_and_partials(file))
+        .sum()
+}
+
+fn remove_file_and_partials(file: &Path) -> usize {
+    let (Some(dir), Some(name)) = (file.parent(), file.file_name()) else {
+        return 0;
+    };
+    let name = name.to_string_lossy();
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| is_partial_of(&entry.file_name().to_string_lossy(), &name))
+        .filter(|entry| fs::remove_file(entry.path()).is_ok())
+        .count()
+}
+
+// The file itself, Title.mp4.part, Title.mp4.ytdl or a fragment like Title.mp4.part-Frag12
+fn is_partial_of(file: &str, name: &str) -> bool {
+    file.strip_prefix(name).is_some_and(|suffix| {
+        suffix.is_empty() || PARTIAL_SUFFIXES.contains(&suffix) || suffix.starts_with(".part-Frag")
+    })
+}
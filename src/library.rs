@@ -0,0 +1,80 @@
+// library.rs
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+// How many folders deep the scan goes, album mode writes Artist/Album/track
+const MAX_DEPTH: usize = 3;
+
+// Extensions of the files yt-dlp and the encode profiles produce
+const MEDIA_EXTENSIONS: &[&str] = &[
+    "mp4", "mkv", "webm", "mov", "avi", "flv", "m4v", "gif", "mp3", "m4a", "opus", "ogg", "flac",
+    "wav", "aac",
+];
+
+pub struct LibraryFile {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: i64, // Unix timestamp
+}
+
+impl LibraryFile {
+    pub fn name(&self) -> String {
+        self.path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string()
+    }
+}
+
+// Media files in the directory and its sub directories, newest first
+pub fn scan(dir: &Path) -> Vec<LibraryFile> {
+    let mut files = Vec::new();
+    scan_in(dir, MAX_DEPTH, &mut files);
+    files.sort_by_key(|file| std::cmp::Reverse(file.modified));
+    files
+}
+
+fn scan_in(dir: &Path, depth: usize, files: &mut Vec<LibraryFile>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+
+        if metadata.is_dir() {
+            // Skip hidden folders like .git or caches
+            let hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if depth > 0 && !hidden {
+                scan_in(&path, depth - 1, files);
+            }
+            continue;
+        }
+
+        let is_media = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .is_some_and(|ext| MEDIA_EXTENSIONS.contains(&ext.as_str()));
+        if !is_media {
+            continue;
+        }
+
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+
+        files.push(LibraryFile {
+            path,
+            size: metadata.len(),
+            modified,
+        });
+    }
+}
@@ -0,0 +1,304 @@
+// live_chat.rs
+use crate::export::csv_field;
+use crate::formats::format_duration;
+use crate::logs::{self, LogSender};
+use chrono::{Local, TimeZone};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process::Command};
+
+pub type LiveChatResult = Result<(usize, Vec<PathBuf>), String>;
+
+// Chat item renderers worth keeping and what they are called in the exports
+const RENDERERS: [(&str, &str); 4] = [
+    ("liveChatTextMessageRenderer", "message"),
+    ("liveChatPaidMessageRenderer", "super chat"),
+    ("liveChatPaidStickerRenderer", "super sticker"),
+    ("liveChatMembershipItemRenderer", "membership"),
+];
+
+#[derive(Debug, Clone)]
+pub struct ChatMessage {
+    pub offset: f64,  // Seconds into the stream, negative before it started
+    pub time: String, // Wall clock time the message was sent
+    pub kind: &'static str,
+    pub author: String,
+    pub message: String,
+    pub amount: String, // Super chat or sticker amount with currency, e.g. $5.00
+}
+
+// Download the live chat replay and write a text log and a CSV per video into the folder,
+// returns the number of messages and the files written
+pub fn export(url: &str, folder: &Path, common_args: &[String], log: &LogSender) -> LiveChatResult {
+    // The raw replay goes to a scratch folder, only the readable exports end up in the output
+    let scratch = env::temp_dir().join(format!("ytdlprustygui-live-chat-{}", std::process::id()));
+    fs::create_dir_all(&scratch)
+        .map_err(|e| format!("Failed to create temporary folder: {}", e))?;
+
+    let result = download_and_convert(url, folder, &scratch, common_args, log);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn download_and_convert(
+    url: &str,
+    folder: &Path,
+    scratch: &Path,
+    common_args: &[String],
+    log: &LogSender,
+) -> LiveChatResult {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args)
+        .args([
+            "--skip-download",
+            "--write-subs",
+            "--sub-langs",
+            "live_chat",
+        ])
+        .arg("-o")
+        .arg(scratch.join("%(title)s [%(id)s].%(ext)s"))
+        .arg(url);
+
+    let output = logs::run_logged(&mut cmd, "Live chat", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!(
+            "Fetching live chat failed: {}",
+            output.error_message()
+        ));
+    }
+
+    let mut total = 0;
+    let mut files = Vec::new();
+    let entries = fs::read_dir(scratch).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let Some(stem) = name.strip_suffix(".live_chat.json") else {
+            continue;
+        };
+
+        let replay = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let messages = parse_replay(&replay);
+
+        let text_path = folder.join(format!("{}.live_chat.txt", stem));
+        fs::write(&text_path, to_text(&messages))
+            .map_err(|e| format!("Failed to write {}: {}", text_path.display(), e))?;
+
+        let csv_path = folder.join(format!("{}.live_chat.csv", stem));
+        fs::write(&csv_path, to_csv(&messages))
+            .map_err(|e| format!("Failed to write {}: {}", csv_path.display(), e))?;
+
+        total += messages.len();
+        files.push(text_path);
+        files.push(csv_path);
+    }
+
+    if files.is_empty() {
+        return Err("This video has no live chat replay".to_string());
+    }
+    Ok((total, files))
+}
+
+// The replay has one JSON object per line, each holding the chat actions at a point in the video
+pub fn parse_replay(replay: &str) -> Vec<ChatMessage> {
+    let mut messages = Vec::new();
+
+    for line in replay.lines() {
+        let Ok(json) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let Some(replay_action) = json.get("replayChatItemAction") else {
+            continue;
+        };
+
+        let offset = replay_action
+            .get("videoOffsetTimeMsec")
+            .and_then(|offset| offset.as_str()?.parse::<f64>().ok())
+            .map(|msec| msec / 1000.0)
+            .unwrap_or(0.0);
+
+        let actions = replay_action.get("actions").and_then(Value::as_array);
+        for action in actions.into_iter().flatten() {
+            let Some(item) = action.pointer("/addChatItemAction/item") else {
+                continue;
+            };
+
+            for (key, kind) in RENDERERS {
+                if let Some(renderer) = item.get(key) {
+                    messages.push(parse_renderer(renderer, kind, offset));
+                }
+            }
+        }
+    }
+
+    messages
+}
+
+fn parse_renderer(renderer: &Value, kind: &'static str, offset: f64) -> ChatMessage {
+    let simple_text = |key: &str| {
+        renderer
+            .pointer(&format!("/{}/simpleText", key))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+
+    // Membership items carry their text in the header instead of a message
+    let message = renderer
+        .get("message")
+        .or_else(|| renderer.get("headerSubtext"))
+        .map(runs_text)
+        .unwrap_or_default();
+
+    let time = renderer
+        .get("timestampUsec")
+        .and_then(|usec| usec.as_str()?.parse::<i64>().ok())
+        .and_then(|usec| Local.timestamp_micros(usec).single())
+        .map(|time| time.format("%Y-%m-%d %H:%M:%S").to_string())
+        .unwrap_or_default();
+
+    ChatMessage {
+        offset,
+        time,
+        kind,
+        author: simple_text("authorName"),
+        message,
+        amount: simple_text("purchaseAmountText"),
+    }
+}
+
+// Message text is split into runs of text and emoji
+fn runs_text(message: &Value) -> String {
+    if let Some(text) = message.get("simpleText").and_then(Value::as_str) {
+        return text.to_string();
+    }
+
+    let runs = message.get("runs").and_then(Value::as_array);
+    runs.into_iter()
+        .flatten()
+        .map(|run| {
+            if let Some(text) = run.get("text").and_then(Value::as_str) {
+                return text.to_string();
+            }
+            // Custom emoji have a :shortcut:, standard ones are their own id
+            run.pointer("/emoji/shortcuts/0")
+                .or_else(|| run.pointer("/emoji/emojiId"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string()
+        })
+        .collect()
+}
+
+fn format_offset(offset: f64) -> String {
+    if offset < 0.0 {
+        format!("-{}", format_duration(-offset))
+    } else {
+        format_duration(offset)
+    }
+}
+
+fn to_text(messages: &[ChatMessage]) -> String {
+    let mut text = String::new();
+
+    for message in messages {
+        text.push_str(&format!("[{}] ", format_offset(message.offset)));
+        if !message.amount.is_empty() {
+            text.push_str(&format!("[{} {}] ", message.kind, message.amount));
+        } else if message.kind != "message" {
+            text.push_str(&format!("[{}] ", message.kind));
+        }
+        text.push_str(&format!("{}: {}\n", message.author, message.message));
+    }
+
+    text
+}
+
+fn to_csv(messages: &[ChatMessage]) -> String {
+    let mut csv = String::from("offset,timestamp,kind,author,amount,message\n");
+
+    for message in messages {
+        let fields = [
+            format_offset(message.offset),
+            message.time.clone(),
+            message.kind.to_string(),
+            message.author.clone(),
+            message.amount.clone(),
+            message.message.clone(),
+        ];
+
+        let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Lines of a .live_chat.json replay: a message with an emoji, a super chat, a membership
+    // before the stream started, an ignored renderer and a line that isn't JSON
+    const REPLAY: &str = r#"{"replayChatItemAction":{"actions":[{"addChatItemAction":{"item":{"liveChatTextMessageRenderer":{"message":{"runs":[{"text":"hello "},{"emoji":{"emojiId":"👋","shortcuts":[":wave:"]}}]},"authorName":{"simpleText":"Viewer"}}}}}],"videoOffsetTimeMsec":"65000"}}
+{"replayChatItemAction":{"actions":[{"addChatItemAction":{"item":{"liveChatPaidMessageRenderer":{"message":{"simpleText":"great stream, thanks"},"authorName":{"simpleText":"Fan"},"purchaseAmountText":{"simpleText":"$5.00"}}}}}],"videoOffsetTimeMsec":"3725000"}}
+{"replayChatItemAction":{"actions":[{"addChatItemAction":{"item":{"liveChatMembershipItemRenderer":{"headerSubtext":{"runs":[{"text":"Welcome to the club"}]},"authorName":{"simpleText":"Member"}}}}},{"addChatItemAction":{"item":{"liveChatViewerEngagementMessageRenderer":{"message":{"simpleText":"Chat rules"}}}}}],"videoOffsetTimeMsec":"-30000"}}
+not json
+"#;
+
+    #[test]
+    fn replay_messages_are_parsed() {
+        let messages = parse_replay(REPLAY);
+        let parsed: Vec<(f64, &str, &str, &str, &str)> = messages
+            .iter()
+            .map(|message| {
+                (
+                    message.offset,
+                    message.kind,
+                    message.author.as_str(),
+                    message.message.as_str(),
+                    message.amount.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            parsed,
+            vec![
+                (65.0, "message", "Viewer", "hello :wave:", ""),
+                (3725.0, "super chat", "Fan", "great stream, thanks", "$5.00"),
+                (-30.0, "membership", "Member", "Welcome to the club", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn text_log_shows_stream_time_and_kind() {
+        let text = to_text(&parse_replay(REPLAY));
+        assert_eq!(
+            text,
+            "[1:05] Viewer: hello :wave:\n\
+             [1:02:05] [super chat $5.00] Fan: great stream, thanks\n\
+             [-0:30] [membership] Member: Welcome to the club\n"
+        );
+    }
+
+    #[test]
+    fn csv_quotes_messages_with_commas() {
+        let csv = to_csv(&parse_replay(REPLAY));
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "offset,timestamp,kind,author,amount,message");
+        assert_eq!(lines[1], "1:05,,message,Viewer,,hello :wave:");
+        assert_eq!(
+            lines[2],
+            "1:02:05,,super chat,Fan,$5.00,\"great stream, thanks\""
+        );
+        assert_eq!(lines.len(), 4);
+    }
+}
@@ -0,0 +1,178 @@
+// logs.rs
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+pub type LogSender = Sender<LogLine>;
+
+// Process id of a running command, shared with the UI so it can be interrupted
+pub type ProcessId = Arc<Mutex<Option<u32>>>;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Verbosity {
+    Quiet, // --quiet, only errors
+    #[default]
+    Normal, // Regular progress output
+    Verbose, // --verbose, debug output for bug reports
+}
+
+// One line printed by a yt-dlp process
+#[derive(Debug, Clone)]
+pub struct LogLine {
+    pub time: String,
+    pub source: String, // Which job printed the line
+    pub is_error: bool, // Printed on stderr
+    pub text: String,
+}
+
+impl LogLine {
+    pub fn new(source: &str, is_error: bool, text: &str) -> Self {
+        Self {
+            time: Local::now().format("%H:%M:%S").to_string(),
+            source: source.to_string(),
+            is_error,
+            text: text.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for LogLine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} [{}] {}", self.time, self.source, self.text)
+    }
+}
+
+impl Verbosity {
+    // Only for commands whose stdout is not parsed, --print and -J imply --quiet otherwise
+    pub fn args(&self) -> Vec<String> {
+        let args: &[&str] = match self {
+            Verbosity::Quiet => &["--quiet"],
+            Verbosity::Normal => &["--no-quiet", "--newline"],
+            Verbosity::Verbose => &["--no-quiet", "--newline", "--verbose"],
+        };
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+}
+
+pub struct CommandOutput {
+    pub success: bool,
+    pub stdout: Vec<String>,
+    pub stderr: Vec<String>,
+}
+
+impl CommandOutput {
+    // The lines worth showing when the command failed
+    pub fn error_message(&self) -> String {
+        let errors: Vec<&str> = self
+            .stderr
+            .iter()
+            .filter(|line| line.starts_with("ERROR"))
+            .map(|line| line.as_str())
+            .collect();
+
+        if errors.is_empty() {
+            self.stderr.join("\n")
+        } else {
+            errors.join("\n")
+        }
+    }
+}
+
+// Run a command while streaming its output to the log console line by line
+pub fn run_logged(
+    cmd: &mut Command,
+    source: &str,
+    log: &LogSender,
+    log_stdout: bool,
+) -> Result<CommandOutput, String> {
+    run_logged_with(cmd, source, log, log_stdout, None, |_| {})
+}
+
+// Like run_logged, also handing each stdout line to on_line as soon as it is printed
+pub fn run_logged_with(
+    cmd: &mut Command,
+    source: &str,
+    log: &LogSender,
+    log_stdout: bool,
+    process: Option<&ProcessId>,
+    mut on_line: impl FnMut(&str),
+) -> Result<CommandOutput, String> {
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| e.to_string())?;
+
+    if let Some(process) = process
+        && let Ok(mut id) = process.lock()
+    {
+        *id = Some(child.id());
+    }
+
+    // Read stderr on its own thread so neither pipe can fill up and block yt-dlp
+    let stderr = child.stderr.take().map(|stderr| {
+        let log = log.clone();
+        let source = source.to_string();
+        thread::spawn(move || {
+            let mut lines = Vec::new();
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                let _ = log.send(LogLine::new(&source, true, &line));
+                lines.push(line);
+            }
+            lines
+        })
+    });
+
+    let mut stdout = Vec::new();
+    if let Some(out) = child.stdout.take() {
+        for line in BufReader::new(out).lines().map_while(Result::ok) {
+            if log_stdout {
+                let _ = log.send(LogLine::new(source, false, &line));
+            }
+            on_line(&line);
+            stdout.push(line);
+        }
+    }
+
+    let stderr = stderr
+        .and_then(|handle| handle.join().ok())
+        .unwrap_or_default();
+    let status = child.wait().map_err(|e| e.to_string());
+
+    if let Some(process) = process
+        && let Ok(mut id) = process.lock()
+    {
+        *id = None;
+    }
+    let status = status?;
+
+    Ok(CommandOutput {
+        success: status.success(),
+        stdout,
+        stderr,
+    })
+}
+
+// Stop a running process like Ctrl+C would, so yt-dlp can finish the file it is writing
+pub fn interrupt(pid: u32) -> Result<(), String> {
+    // Windows can't send Ctrl+C to a process without a console, it can only be ended
+    let result = if cfg!(target_os = "windows") {
+        Command::new("taskkill")
+            .args(["/PID", &pid.to_string(), "/T", "/F"])
+            .status()
+    } else {
+        Command::new("kill")
+            .args(["-INT", &pid.to_string()])
+            .status()
+    };
+
+    match result {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err(format!("Failed to stop process {}", pid)),
+        Err(e) => Err(format!("Failed to stop process {}: {}", pid, e)),
+    }
+}
@@ -1,584 +1,667 @@
 // main.rs
-use eframe::egui::{Button, CentralPanel, Color32, ComboBox, RichText, Vec2};
+mod album;
+mod appearance;
+mod channel;
+mod comments;
+mod config_files;
+mod desktop;
+mod download;
+mod encode;
+mod export;
+mod extra_args;
+mod format_table;
+mod formats;
+mod history;
+mod jobs;
+mod library;
+mod live_chat;
+mod logs;
+mod search;
+mod settings;
+mod shortcuts;
+mod subscriptions;
+mod transcript;
+mod views;
+
+use appearance::{AppearanceSettings, FontsResult, Status};
+use download::{AudioProcessing, DownloadResult};
+use eframe::egui::{CentralPanel, Color32, DragValue};
 use eframe::{NativeOptions, egui};
-use rfd::FileDialog;
+use encode::EncodeEvent;
+use formats::{LiveStatus, VideoInfo};
+use history::HistoryEntry;
+use jobs::Job;
+use logs::{LogLine, LogSender};
+use settings::{CompletionAction, Settings};
 use std::sync::mpsc::{Receiver, channel};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{error::Error, path::PathBuf, process::Command, thread};
+use subscriptions::Subscription;
+use views::{
+    ChannelView, DownloadView, HistoryView, LibraryView, LogView, QueueView, SearchView,
+    SettingsView, ShortcutsView, SubscriptionsView, View,
+};
 
-type FormatResult = Result<(Vec<Format>, Vec<Format>), String>;
+type FormatResult = Result<VideoInfo, String>;
 type FormatReceiver = Receiver<FormatResult>;
-type DownloadResult = Result<(), String>;
 type DownloadReceiver = Receiver<DownloadResult>;
 
 struct YtDlpGUI {
-    url: String,
-    output_dir: String,
-    selected_video_format: String,
-    selected_audio_format: String,
-    available_video_formats: Vec<Format>,
-    available_audio_formats: Vec<Format>,
+    view: View,
     status_message: String,
-    status_color: Color32,
+    status: Status,
     is_processing: bool,
-    format_receiver: Option<FormatReceiver>,
-    download_receiver: Option<DownloadReceiver>,
+    downloaded_bytes: u64,
+    recording_since: Option<i64>, // When the first bytes of a live recording arrived
+    stop_requested: bool,
+    close_requested: bool,
     download_progress: f32,
-    download_type: DownloadType,
-}
-
-#[derive(Debug, Clone, PartialEq)]
-enum DownloadType {
-    VideoAudio, // Combined video+audio to MP4
-    AudioOnly,  // Audio only as MP3
-}
-
-#[derive(Debug, Clone)]
-struct Format {
-    id: String,
-    extension: String,
-    resolution: String,
-    description: String,
-    is_video: bool, // Flag to indicate if this is a video format
-    is_audio: bool, // Flag to indicate if this is an audio format
+    settings: Settings,
+    saved_settings: Settings,
+    applied_appearance: Option<AppearanceSettings>, // None until the first frame
+    fonts_receiver: Option<Receiver<FontsResult>>,
+    log_sender: LogSender,
+    log_receiver: Receiver<LogLine>,
+    download: DownloadView,
+    queue: QueueView,
+    history: HistoryView,
+    library: LibraryView,
+    search: SearchView,
+    channel: ChannelView,
+    subscriptions: SubscriptionsView,
+    log: LogView,
+    settings_view: SettingsView,
+    shortcuts: ShortcutsView,
 }
 
 impl YtDlpGUI {
-    fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        // Lets egui::Image load search thumbnails straight from their URLs
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+
         // Default to user's home directory for downloads
         let output_dir = dirs::home_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .to_string_lossy()
             .to_string();
 
+        let settings = Settings::load();
+        let settings_view = SettingsView::new(&settings.auth);
+        let (log_sender, log_receiver) = channel();
+        let interrupted_jobs = jobs::load();
+        let status_message = if interrupted_jobs.is_empty() {
+            String::from("Ready")
+        } else {
+            format!(
+                "{} interrupted download(s) can be resumed",
+                interrupted_jobs.len()
+            )
+        };
+        let new_subscription = Subscription::new(
+            next_subscription_id(&settings),
+            &settings
+                .presets
+                .first()
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            &output_dir,
+        );
+        let queue_preset = new_subscription.preset.clone();
+
         Self {
-            url: String::new(),
-            output_dir,
-            selected_video_format: String::new(),
-            selected_audio_format: String::new(),
-            available_video_formats: Vec::new(),
-            available_audio_formats: Vec::new(),
-            status_message: String::from("Ready"),
-            status_color: Color32::GRAY,
+            // Interrupted downloads are listed with the queue
+            view: if interrupted_jobs.is_empty() {
+                View::Download
+            } else {
+                View::Queue
+            },
+            status_message,
+            status: Status::Info,
             is_processing: false,
-            format_receiver: None,
-            download_receiver: None,
+            downloaded_bytes: 0,
+            recording_since: None,
+            stop_requested: false,
+            close_requested: false,
             download_progress: 0.0,
-            download_type: DownloadType::VideoAudio,
+            saved_settings: settings.clone(),
+            applied_appearance: None,
+            fonts_receiver: None,
+            settings,
+            log_sender,
+            log_receiver,
+            download: DownloadView::new(output_dir),
+            queue: QueueView::new(queue_preset, interrupted_jobs),
+            history: HistoryView::new(),
+            library: LibraryView::new(),
+            search: SearchView::new(),
+            channel: ChannelView::new(),
+            subscriptions: SubscriptionsView::new(new_subscription),
+            log: LogView::new(),
+            settings_view,
+            shortcuts: ShortcutsView::new(),
         }
     }
 
-    fn fetch_formats(&mut self) {
-        if self.url.is_empty() {
-            self.set_status("Please enter a URL first", Color32::RED);
-            return;
-        }
+    // Arguments every download job gets in addition to its request
+    fn job_args(&self) -> Vec<String> {
+        let mut args = self.settings.common_args();
+        args.extend(self.settings.verbosity.args());
+        args.extend(self.settings.notifications.exec_args());
+        args
+    }
 
-        self.set_status("Fetching available formats...", Color32::YELLOW);
+    fn start_job(&mut self, job: Job) {
         self.is_processing = true;
+        self.download_progress = 0.0;
+        self.downloaded_bytes = 0;
+        self.recording_since = None;
+        self.stop_requested = false;
+        self.download.output_files.clear();
+
+        let request = job.request.clone();
+        let common_args = self.job_args();
+        let source = format!("Download #{}", job.id);
+        let log = self.log_sender.clone();
+        let process = self.download.download_process.clone();
+
+        if request.live.is_some() {
+            self.set_status("Recording live stream...", Status::Working);
+        } else {
+            self.set_status("Downloading...", Status::Working);
+        }
 
-        // Clone values for the thread
-        let url = self.url.clone();
+        // Keep the job on disk until it finishes so it can be resumed after a restart
+        self.queue.current_job = Some(job);
+        self.save_jobs();
 
-        // Create a channel to receive results
+        // Create channels for download results and progress
         let (sender, receiver) = channel();
-        self.format_receiver = Some(receiver);
+        self.download.download_receiver = Some(receiver);
+        let (progress_sender, progress_receiver) = channel();
+        self.download.progress_receiver = Some(progress_receiver);
 
-        // Spawn a new thread to run yt-dlp
+        // Spawn a new thread for downloading
         thread::spawn(move || {
-            // Run yt-dlp to get format information
-            let output = Command::new("yt-dlp")
-                .args(["--list-formats", &url])
-                .output();
-
-            match output {
-                Ok(output) => {
-                    if !output.status.success() {
-                        let error = String::from_utf8_lossy(&output.stderr).to_string();
-                        sender
-                            .send(Err(format!("Failed to fetch formats: {}", error)))
-                            .unwrap();
-                        return;
-                    }
-
-                    let output_str = String::from_utf8_lossy(&output.stdout).to_string();
-                    let formats = parse_formats(&output_str);
+            let result = download::run(
+                &request,
+                &common_args,
+                &source,
+                &log,
+                &process,
+                &progress_sender,
+            );
+            let _ = sender.send(result);
+        });
+    }
 
-                    // Separate video and audio formats
-                    let video_formats: Vec<Format> =
-                        formats.iter().filter(|f| f.is_video).cloned().collect();
+    fn is_recording(&self) -> bool {
+        self.queue
+            .current_job
+            .as_ref()
+            .is_some_and(|job| job.request.live.is_some())
+    }
 
-                    let audio_formats: Vec<Format> =
-                        formats.iter().filter(|f| f.is_audio).cloned().collect();
+    // Interrupt yt-dlp so it stops recording and finishes the file it has so far
+    fn stop_recording(&mut self) {
+        let pid = self
+            .download
+            .download_process
+            .lock()
+            .ok()
+            .and_then(|id| *id);
+        let Some(pid) = pid else {
+            return;
+        };
 
-                    sender.send(Ok((video_formats, audio_formats))).unwrap();
-                }
-                Err(e) => {
-                    sender
-                        .send(Err(format!("Failed to execute yt-dlp: {}", e)))
-                        .unwrap();
-                }
+        match logs::interrupt(pid) {
+            Ok(()) => {
+                self.stop_requested = true;
+                self.set_status("Stopping, finishing the recording...", Status::Working);
             }
-        });
+            Err(e) => self.set_status(&e, Status::Error),
+        }
     }
 
-    fn download(&mut self) {
-        if self.url.is_empty() {
-            self.set_status("Please enter a URL first", Color32::RED);
+    // Interrupt a normal download, the job moves to the interrupted ones to be resumed or discarded
+    fn cancel_download(&mut self) {
+        let pid = self
+            .download
+            .download_process
+            .lock()
+            .ok()
+            .and_then(|id| *id);
+        let Some(pid) = pid else {
             return;
+        };
+
+        match logs::interrupt(pid) {
+            Ok(()) => {
+                self.stop_requested = true;
+                self.set_status("Cancelling download...", Status::Working);
+            }
+            Err(e) => self.set_status(&e, Status::Error),
         }
+    }
 
-        if self.output_dir.is_empty() {
-            self.set_status("Please select an output directory", Color32::RED);
+    // Run the selected encode profile over the files of the last download
+    fn encode(&mut self) {
+        let Some(profile) = self
+            .settings
+            .encode_profiles
+            .iter()
+            .find(|profile| profile.name == self.download.encode_profile)
+            .cloned()
+        else {
+            self.set_status("Select an encode profile first", Status::Error);
             return;
-        }
+        };
 
-        // For video+audio mode, we need both formats selected
-        if self.download_type == DownloadType::VideoAudio
-            && (self.selected_video_format.is_empty()
-                || self.selected_audio_format.is_empty()
-                || self.available_video_formats.is_empty()
-                || self.available_audio_formats.is_empty())
-        {
-            self.set_status(
-                "Please fetch and select both video and audio formats",
-                Color32::RED,
-            );
+        if self.download.output_files.is_empty() {
+            self.set_status("Nothing to encode, download something first", Status::Error);
             return;
         }
 
         self.is_processing = true;
-        self.download_progress = 0.0;
-
-        // Prepare download command arguments
-        let url = self.url.clone();
-        let output_dir = self.output_dir.clone();
-        let selected_video_format = self.selected_video_format.clone();
-        let selected_audio_format = self.selected_audio_format.clone();
-        let download_type = self.download_type.clone();
+        self.download.encode_progress = 0.0;
+        self.set_status(
+            &format!("Encoding with {}...", profile.name),
+            Status::Working,
+        );
 
-        self.set_status("Downloading...", Color32::YELLOW);
+        let files = self.download.output_files.clone();
+        let keep_original = self.settings.keep_original;
+        let log = self.log_sender.clone();
 
-        // Create a channel for download results
         let (sender, receiver) = channel();
-        self.download_receiver = Some(receiver);
+        self.download.encode_receiver = Some(receiver);
 
-        // Spawn a new thread for downloading
         thread::spawn(move || {
-            let mut cmd = Command::new("yt-dlp");
-
-            match download_type {
-                DownloadType::VideoAudio => {
-                    // For video+audio MP4 download with separate format selection
-                    let format_spec =
-                        format!("{}+{}", selected_video_format, selected_audio_format);
-                    cmd.args([
-                        "-f",
-                        &format_spec,
-                        "-o",
-                        &format!("{}/%(title)s.%(ext)s", output_dir),
-                        "--merge-output-format",
-                        "mp4",
-                        &url,
-                    ]);
-                }
-                DownloadType::AudioOnly => {
-                    // For audio-only MP3 download
-                    cmd.args([
-                        "-x",
-                        "--audio-format",
-                        "mp3",
-                        "-o",
-                        &format!("{}/%(title)s.%(ext)s", output_dir),
-                        &url,
-                    ]);
-                }
-            }
-
-            match cmd.output() {
-                Ok(output) => {
-                    if output.status.success() {
-                        let _ = sender.send(Ok(()));
-                    } else {
-                        let error = String::from_utf8_lossy(&output.stderr).to_string();
-                        let _ = sender.send(Err(format!("Download failed: {}", error)));
-                    }
-                }
-                Err(e) => {
-                    sender
-                        .send(Err(format!("Failed to execute yt-dlp: {}", e)))
-                        .unwrap();
-                }
-            }
+            encode::run(&files, &profile, keep_original, &sender, &log);
         });
     }
 
-    fn set_status(&mut self, message: &str, color: Color32) {
-        self.status_message = message.to_string();
-        self.status_color = color;
+    fn next_job_id(&self) -> u64 {
+        self.queue
+            .interrupted_jobs
+            .iter()
+            .chain(self.queue.current_job.iter())
+            .map(|job| job.id + 1)
+            .max()
+            .unwrap_or(0)
     }
 
-    fn browse_output_dir(&mut self) {
-        if let Some(folder) = FileDialog::new()
-            .set_directory(&self.output_dir)
-            .pick_folder()
-        {
-            self.output_dir = folder.to_string_lossy().to_string();
+    fn save_jobs(&mut self) {
+        let mut jobs = self.queue.interrupted_jobs.clone();
+        jobs.extend(self.queue.current_job.clone());
+
+        if let Err(e) = jobs::save(&jobs) {
+            self.set_status(&e, Status::Error);
         }
     }
 
-    fn handle_clipboard_paste(&mut self) {
-        if let Ok(mut clipboard) = arboard::Clipboard::new() {
-            if let Ok(text) = clipboard.get_text() {
-                self.url = text;
-            } else {
-                self.set_status("Failed to paste from clipboard", Color32::RED);
-            }
-        } else {
-            self.set_status("Clipboard access failed", Color32::RED);
-        }
+    fn set_status(&mut self, message: &str, status: Status) {
+        self.status_message = message.to_string();
+        self.status = status;
     }
 
-    fn check_receivers(&mut self) {
-        // Check format receiver
-        if let Some(receiver) = &self.format_receiver {
-            if let Ok(result) = receiver.try_recv() {
-                match result {
-                    Ok((video_formats, audio_formats)) => {
-                        self.available_video_formats = video_formats;
-                        self.available_audio_formats = audio_formats;
-
-                        if !self.available_video_formats.is_empty() {
-                            self.selected_video_format = self.available_video_formats[0].id.clone();
-                        }
-
-                        if !self.available_audio_formats.is_empty() {
-                            self.selected_audio_format = self.available_audio_formats[0].id.clone();
-                        }
-
-                        if !self.available_video_formats.is_empty()
-                            && !self.available_audio_formats.is_empty()
-                        {
-                            self.set_status("Formats fetched successfully", Color32::GREEN);
-                        } else {
-                            self.set_status(
-                                "No formats available or could not distinguish audio/video formats",
-                                Color32::RED,
-                            );
-                        }
-                    }
-                    Err(e) => {
-                        self.set_status(&e, Color32::RED);
-                    }
+    fn status_color(&self, status: Status) -> Color32 {
+        self.settings.appearance.status_colors.color(status)
+    }
+
+    // Persist whenever something was changed in one of the views
+    fn save_settings_if_changed(&mut self) {
+        if self.settings != self.saved_settings {
+            match self.settings.save() {
+                Ok(()) => self.saved_settings = self.settings.clone(),
+                Err(e) => {
+                    self.set_status(&e, Status::Error);
+                    self.saved_settings = self.settings.clone();
                 }
+            }
+        }
+    }
 
-                self.is_processing = false;
-                self.format_receiver = None;
+    // Theme, zoom and fonts follow the settings, Ctrl +/- zooms as well
+    fn apply_appearance(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &self.fonts_receiver
+            && let Ok((fonts, result)) = receiver.try_recv()
+        {
+            self.fonts_receiver = None;
+            ctx.set_fonts(fonts);
+            if let Err(e) = result {
+                self.set_status(&e, Status::Error);
             }
         }
 
-        // Check download receiver
-        if let Some(receiver) = &self.download_receiver {
-            if let Ok(result) = receiver.try_recv() {
-                match result {
-                    Ok(()) => {
-                        self.set_status("Download completed successfully", Color32::GREEN);
-                        self.download_progress = 1.0;
-                    }
-                    Err(e) => {
-                        self.set_status(&e, Color32::RED);
-                    }
-                }
+        if let Some(applied) = &mut self.applied_appearance {
+            let zoom = ctx.zoom_factor();
+            if zoom != applied.zoom {
+                applied.zoom = zoom;
+                self.settings.appearance.zoom = zoom;
+            }
 
-                self.is_processing = false;
-                self.download_receiver = None;
+            if *applied == self.settings.appearance {
+                return;
             }
         }
-    }
-}
-
-impl eframe::App for YtDlpGUI {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Check for updates from background threads
-        self.check_receivers();
 
-        CentralPanel::default().show(ctx, |ui| {
-            ui.heading("YT-DLP Rusty GUI");
+        let appearance = self.settings.appearance.clone();
+        appearance.apply(ctx);
 
-            // URL input with paste button
-            ui.horizontal(|ui| {
-                ui.label("URL:");
-                ui.text_edit_singleline(&mut self.url).lost_focus();
+        // Fonts are only loaded again when they change, that's slow
+        let fonts_changed = self
+            .applied_appearance
+            .as_ref()
+            .is_none_or(|applied| appearance.fonts_changed(applied));
+        if fonts_changed {
+            self.load_fonts(ctx, &appearance);
+        }
 
-                if ui.button("Paste").clicked() {
-                    self.handle_clipboard_paste();
-                }
-            });
+        self.applied_appearance = Some(appearance);
+    }
 
-            // Output directory selection
-            ui.horizontal(|ui| {
-                ui.label("Output Directory:");
-                ui.text_edit_singleline(&mut self.output_dir);
+    // Read the font files on another thread, the fonts are swapped in once they are ready.
+    // A newer request replaces one still being read
+    fn load_fonts(&mut self, ctx: &egui::Context, settings: &AppearanceSettings) {
+        let (sender, receiver) = channel();
+        self.fonts_receiver = Some(receiver);
 
-                if ui.button("Browse").clicked() {
-                    self.browse_output_dir();
-                }
-            });
+        let settings = settings.clone();
+        let ctx = ctx.clone();
+        thread::spawn(move || {
+            let _ = sender.send(appearance::read_fonts(&settings));
+            ctx.request_repaint();
+        });
+    }
 
-            // Download type selection
-            ui.horizontal(|ui| {
-                ui.label("Download Type:");
-
-                ui.radio_value(
-                    &mut self.download_type,
-                    DownloadType::VideoAudio,
-                    "Video+Audio (MP4)",
-                );
-                ui.radio_value(
-                    &mut self.download_type,
-                    DownloadType::AudioOnly,
-                    "Audio Only (MP3)",
-                );
-            });
+    fn notify(&self, summary: &str, body: &str, success: bool) {
+        let notifications = &self.settings.notifications;
 
-            // Format fetching and selection (only shown for video+audio mode)
-            if self.download_type == DownloadType::VideoAudio {
-                ui.horizontal(|ui| {
-                    let fetch_button = ui.add_enabled(
-                        !self.is_processing && !self.url.is_empty(),
-                        Button::new("Fetch Formats"),
-                    );
+        if (success && notifications.notify_success) || (!success && notifications.notify_failure) {
+            desktop::notify(summary, body, success, notifications.sound);
+        }
+    }
 
-                    if fetch_button.clicked() {
-                        self.fetch_formats();
-                    }
-                });
+    // Opening or running something on the file itself is done by yt-dlp through --exec
+    fn run_completion_action(&mut self) {
+        match self.settings.notifications.action {
+            CompletionAction::OpenFolder => {
+                let result = match self.download.output_files.first() {
+                    Some(file) => desktop::show_in_folder(file),
+                    None => desktop::open_path(
+                        &self
+                            .queue
+                            .current_job
+                            .as_ref()
+                            .map(|job| job.request.output_dir.clone())
+                            .unwrap_or_else(|| self.download.output_dir.clone()),
+                    ),
+                };
+
+                if let Err(e) = result {
+                    self.set_status(&e, Status::Error);
+                }
+            }
+            CompletionAction::CloseWhenQueueDone => self.close_requested = true,
+            _ => {}
+        }
+    }
 
-                // Only show format selection when formats are available
-                if !self.available_video_formats.is_empty()
-                    && !self.available_audio_formats.is_empty()
+    fn check_receivers(&mut self) {
+        self.collect_log_lines();
+        self.check_download_receivers();
+        self.check_search_receiver();
+        self.check_channel_receiver();
+        self.check_library_receiver();
+
+        // Progress of the running download
+        if let Some(receiver) = &self.download.progress_receiver {
+            let mut new_files = false;
+            while let Ok(progress) = receiver.try_recv() {
+                self.downloaded_bytes = progress.downloaded;
+                if let (Some(file), Some(job)) = (progress.file, self.queue.current_job.as_mut())
+                    && !job.files.contains(&file)
                 {
-                    // Video format selection
-                    ui.horizontal(|ui| {
-                        ui.label("Video Format:");
-
-                        ComboBox::new("video_format_combo", "")
-                            .selected_text(format!(
-                                "{} ({})",
-                                self.selected_video_format,
-                                self.available_video_formats
-                                    .iter()
-                                    .find(|f| f.id == self.selected_video_format)
-                                    .map(|f| f.description.as_str())
-                                    .unwrap_or("")
-                            ))
-                            .show_ui(ui, |ui| {
-                                for format in &self.available_video_formats {
-                                    ui.selectable_value(
-                                        &mut self.selected_video_format,
-                                        format.id.clone(),
-                                        format!(
-                                            "{} - {} ({})",
-                                            format.id, format.resolution, format.extension
-                                        ),
-                                    );
-                                }
-                            });
-                    });
-
-                    // Audio format selection
-                    ui.horizontal(|ui| {
-                        ui.label("Audio Format:");
-
-                        ComboBox::new("audio_format_combo", "")
-                            .selected_text(format!(
-                                "{} ({})",
-                                self.selected_audio_format,
-                                self.available_audio_formats
-                                    .iter()
-                                    .find(|f| f.id == self.selected_audio_format)
-                                    .map(|f| f.description.as_str())
-                                    .unwrap_or("")
-                            ))
-                            .show_ui(ui, |ui| {
-                                for format in &self.available_audio_formats {
-                                    ui.selectable_value(
-                                        &mut self.selected_audio_format,
-                                        format.id.clone(),
-                                        format!("{} - {}", format.id, format.description),
-                                    );
-                                }
-                            });
-                    });
+                    job.files.push(file);
+                    new_files = true;
+                }
+                if let Some(total) = progress.total.filter(|total| *total > 0) {
+                    self.download_progress = progress.downloaded as f32 / total as f32;
+                }
+                if self.recording_since.is_none() {
+                    self.recording_since = Some(unix_now());
                 }
             }
-
-            // Download button
-            if ui
-                .add_enabled(!self.is_processing, Button::new("Download"))
-                .clicked()
-            {
-                self.download();
+            if new_files {
+                self.save_jobs();
             }
+        }
 
-            // Progress indicator (simple for now)
-            if self.is_processing {
-                ui.spinner();
+        // Check download receiver
+        if let Some(receiver) = &self.download.download_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            let url = self
+                .queue
+                .current_job
+                .as_ref()
+                .map(|job| job.request.url.clone())
+                .unwrap_or_default();
+            let success = result.is_ok();
+            let recording = self.is_recording();
+            let cancelled = !success && self.stop_requested && !recording;
+
+            match result {
+                Ok(files) => {
+                    self.download.output_files = files;
+                    if self.stop_requested && recording {
+                        self.set_status("Recording saved", Status::Success);
+                    } else {
+                        self.set_status("Download completed successfully", Status::Success);
+                    }
+                    self.download_progress = 1.0;
+                    self.download.free_space_dir.clear();
+                    self.notify("Download completed", &url, true);
+                }
+                Err(_) if cancelled => {
+                    self.set_status(
+                        "Download cancelled, resume or discard it in the Queue tab",
+                        Status::Warning,
+                    );
+                }
+                // yt-dlp can't always finish the file after being interrupted
+                Err(_) if self.stop_requested => {
+                    self.set_status(
+                        "Recording stopped, the partial file is in the output directory",
+                        Status::Warning,
+                    );
+                }
+                Err(e) => {
+                    self.set_status(&e, Status::Error);
+                    self.notify("Download failed", &url, false);
+                }
             }
 
-            // Status message
-            ui.horizontal(|ui| {
-                ui.label("Status: ");
-                ui.label(RichText::new(&self.status_message).color(self.status_color));
+            self.add_history(HistoryEntry {
+                url,
+                files: if success {
+                    self.download.output_files.clone()
+                } else {
+                    Vec::new()
+                },
+                finished: unix_now(),
+                success,
+                message: self.status_message.clone(),
             });
 
-            // Format list display
-            if (!self.available_video_formats.is_empty()
-                || !self.available_audio_formats.is_empty())
-                && self.download_type == DownloadType::VideoAudio
-            {
-                // Video formats
-                if !self.available_video_formats.is_empty() {
-                    ui.collapsing("Available Video Formats", |ui| {
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new("ID").strong());
-                            ui.add_space(50.0);
-                            ui.label(RichText::new("Extension").strong());
-                            ui.add_space(30.0);
-                            ui.label(RichText::new("Resolution").strong());
-                            ui.add_space(30.0);
-                            ui.label(RichText::new("Description").strong());
-                        });
-
-                        ui.separator();
-
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            for format in &self.available_video_formats {
-                                ui.horizontal(|ui| {
-                                    ui.label(&format.id);
-                                    ui.add_space(50.0);
-                                    ui.label(&format.extension);
-                                    ui.add_space(30.0);
-                                    ui.label(&format.resolution);
-                                    ui.add_space(30.0);
-                                    ui.label(&format.description);
-                                });
-                            }
-                        });
-                    });
-                }
+            self.is_processing = false;
+            self.download.download_receiver = None;
+            self.download.progress_receiver = None;
+            self.stop_requested = false;
+            // The partial files of a cancelled job stay until it is resumed or discarded
+            let job = self.queue.current_job.take();
+            if cancelled {
+                self.queue.interrupted_jobs.extend(job);
+            }
+            self.save_jobs();
+
+            if success {
+                self.run_completion_action();
+            }
+
+            if !self.download.encode_profile.is_empty() && !self.download.output_files.is_empty() {
+                self.encode();
+            }
+        }
 
-                // Audio formats
-                if !self.available_audio_formats.is_empty() {
-                    ui.collapsing("Available Audio Formats", |ui| {
-                        ui.horizontal(|ui| {
-                            ui.label(RichText::new("ID").strong());
-                            ui.add_space(50.0);
-                            ui.label(RichText::new("Extension").strong());
-                            ui.add_space(30.0);
-                            ui.label(RichText::new("Description").strong());
-                        });
-
-                        ui.separator();
-
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            for format in &self.available_audio_formats {
-                                ui.horizontal(|ui| {
-                                    ui.label(&format.id);
-                                    ui.add_space(50.0);
-                                    ui.label(&format.extension);
-                                    ui.add_space(30.0);
-                                    ui.label(&format.description);
-                                });
-                            }
-                        });
-                    });
+        // Check encode receiver, ffmpeg reports progress many times per file
+        let mut encode_result = None;
+        if let Some(receiver) = &self.download.encode_receiver {
+            while let Ok(event) = receiver.try_recv() {
+                match event {
+                    EncodeEvent::Progress(progress) => self.download.encode_progress = progress,
+                    EncodeEvent::Finished(result) => encode_result = Some(result),
+                }
+            }
+        }
+        if let Some(result) = encode_result {
+            match result {
+                Ok(files) => {
+                    self.download.output_files = files;
+                    self.set_status("Encoding completed successfully", Status::Success);
+                    self.notify("Encoding completed", &self.download.encode_profile, true);
+                }
+                Err(e) => {
+                    self.set_status(&e, Status::Error);
+                    self.notify("Encoding failed", &e, false);
                 }
             }
-        });
 
-        // Request repaint if we're processing to keep checking receivers
-        if self.is_processing {
-            ctx.request_repaint();
+            self.is_processing = false;
+            self.download.encode_receiver = None;
+            self.download.free_space_dir.clear();
         }
     }
 }
 
-fn parse_formats(output: &str) -> Vec<Format> {
-    let mut formats = Vec::new();
+impl eframe::App for YtDlpGUI {
+    // A password still being typed when the window closes is kept too
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.store_login(false);
+    }
 
-    // Flag to indicate we've reached the format table section
-    let mut in_format_table = false;
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.apply_appearance(ctx);
 
-    for line in output.lines() {
-        // Skip lines until we find the format table header
-        if line.contains("ID") && line.contains("EXT") && line.contains("RESOLUTION") {
-            in_format_table = true;
-            continue;
-        }
+        // Check for updates from background threads
+        self.check_receivers();
+        self.start_next_download();
+        self.refresh_free_space();
+        self.handle_shortcuts(ctx);
 
-        if !in_format_table {
-            continue;
-        }
+        egui::TopBottomPanel::top("view_tabs").show(ctx, |ui| {
+            self.show_view_tabs(ui);
+        });
 
-        // Skip empty lines or lines without format information
-        if line.trim().is_empty() || !line.contains(" ") {
-            continue;
-        }
+        egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+            self.show_status_bar(ui);
+        });
 
-        // Parse format line
-        let parts: Vec<&str> = line.split_whitespace().collect();
-        if parts.len() >= 3 {
-            // The format ID is always the first part
-            let id = parts[0].to_string();
+        CentralPanel::default().show(ctx, |ui| {
+            self.show_current_view(ui);
+        });
 
-            // Extract extension (usually the second part)
-            let extension = if parts.len() > 1 {
-                parts[1].to_string()
-            } else {
-                "unknown".to_string()
-            };
+        self.show_command_window(ctx);
+        self.show_configs_window(ctx);
+        self.show_shortcuts_window(ctx);
 
-            // Extract resolution if available
-            let resolution = if parts.len() > 2 && parts[2].contains("x") {
-                parts[2].to_string()
-            } else {
-                "audio only".to_string()
-            };
+        self.check_subscriptions();
+        self.sync_login_file();
+        self.store_login(ctx.wants_keyboard_input());
+        self.save_settings_if_changed();
 
-            // Join remaining parts as description
-            let description = if parts.len() > 3 {
-                parts[3..].join(" ")
-            } else {
-                String::new()
-            };
-
-            // Detect if this is a video or audio format
-            let is_video = !resolution.contains("audio only")
-                || line.to_lowercase().contains("video only")
-                || (line.contains("mp4") && !line.to_lowercase().contains("audio only"));
-
-            let is_audio = resolution.contains("audio only")
-                || line.to_lowercase().contains("audio only")
-                || extension == "m4a"
-                || extension == "mp3"
-                || extension == "ogg"
-                || extension == "opus";
-
-            // Only add if it's a real format (not a header or separator)
-            if !id.contains("-") && !id.contains("=") {
-                formats.push(Format {
-                    id,
-                    extension,
-                    resolution,
-                    description,
-                    is_video,
-                    is_audio,
-                });
-            }
+        // Keep waking up so scheduled subscriptions are checked on time
+        if self.subscriptions.is_checking() {
+            ctx.request_repaint_after(Duration::from_millis(250));
+        } else if !self.settings.subscriptions.is_empty() {
+            ctx.request_repaint_after(Duration::from_secs(30));
+        }
+
+        // Tick the countdown of an upcoming stream
+        if self.download.live_status == LiveStatus::Upcoming {
+            ctx.request_repaint_after(Duration::from_secs(1));
+        }
+
+        // Queued downloads and encoding run to the end before the window closes
+        if self.close_requested
+            && self.queue.items.is_empty()
+            && self.queue.current_job.is_none()
+            && !self.is_processing
+        {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
+
+        // Request repaint if we're processing to keep checking receivers
+        if self.is_processing
+            || self.download.is_busy()
+            || self.search.receiver.is_some()
+            || self.channel.receiver.is_some()
+            || self.library.receiver.is_some()
+        {
+            ctx.request_repaint();
         }
     }
+}
 
-    formats
+fn show_audio_processing(ui: &mut egui::Ui, audio: &mut AudioProcessing) {
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut audio.normalize, "Normalize loudness (EBU R128)");
+        ui.add_enabled_ui(audio.normalize, |ui| {
+            ui.label("Target:");
+            ui.add(
+                DragValue::new(&mut audio.target_lufs)
+                    .range(-40.0..=-5.0)
+                    .speed(0.5)
+                    .suffix(" LUFS"),
+            );
+            ui.label("True peak:");
+            ui.add(
+                DragValue::new(&mut audio.true_peak)
+                    .range(-9.0..=0.0)
+                    .speed(0.1)
+                    .suffix(" dBTP"),
+            );
+        });
+    });
+
+    ui.horizontal(|ui| {
+        ui.checkbox(&mut audio.trim_silence, "Trim silence at start and end");
+        ui.add_enabled_ui(audio.trim_silence, |ui| {
+            ui.label("Below:");
+            ui.add(
+                DragValue::new(&mut audio.silence_threshold)
+                    .range(-90.0..=-20.0)
+                    .speed(1.0)
+                    .suffix(" dB"),
+            );
+        });
+    });
+}
+
+fn next_subscription_id(settings: &Settings) -> u64 {
+    settings
+        .subscriptions
+        .iter()
+        .map(|s| s.id + 1)
+        .max()
+        .unwrap_or(0)
+}
+
+fn unix_now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0)
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
@@ -592,12 +675,16 @@ fn main() -> Result<(), Box<dyn Error>> {
     }
 
     let mut options = NativeOptions::default();
-    options.viewport.inner_size = Some(Vec2::new(800.0, 600.0));
+    options.viewport.inner_size = Some(egui::Vec2::new(800.0, 600.0));
+    options.viewport.min_inner_size = Some(egui::Vec2::new(360.0, 240.0));
 
-    eframe::run_native(
+    settings::remove_stale_login_files();
+    let result = eframe::run_native(
         "YT-DLP Rusty GUI",
         options,
         Box::new(|cc| Ok(Box::new(YtDlpGUI::new(cc)))),
-    )
-    .map_err(|e| e.into())
+    );
+
+    settings::remove_login_netrc();
+    result.map_err(|e| e.into())
 }
@@ -0,0 +1,113 @@
+// search.rs
+use crate::logs::{self, LogSender};
+use serde_json::Value;
+use std::process::Command;
+
+pub type SearchOutcome = Result<Vec<SearchResult>, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SearchSite {
+    YouTube,
+    YouTubeMusic,
+    SoundCloud,
+}
+
+impl SearchSite {
+    pub const ALL: [SearchSite; 3] = [
+        SearchSite::YouTube,
+        SearchSite::YouTubeMusic,
+        SearchSite::SoundCloud,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SearchSite::YouTube => "YouTube",
+            SearchSite::YouTubeMusic => "YouTube Music",
+            SearchSite::SoundCloud => "SoundCloud",
+        }
+    }
+
+    // yt-dlp's search pseudo-URL, e.g. ytsearch10:query
+    fn url(&self, query: &str, count: u32) -> String {
+        let prefix = match self {
+            SearchSite::YouTube => "ytsearch",
+            SearchSite::YouTubeMusic => "ytmsearch",
+            SearchSite::SoundCloud => "scsearch",
+        };
+        format!("{}{}:{}", prefix, count, query)
+    }
+}
+
+// One entry of a flat search playlist
+#[derive(Debug, Clone)]
+pub struct SearchResult {
+    pub title: String,
+    pub channel: String,
+    pub duration: Option<f64>, // Seconds
+    pub url: String,
+    pub thumbnail: Option<String>,
+}
+
+// Run the search without resolving each result, which keeps it fast
+pub fn search(
+    site: SearchSite,
+    query: &str,
+    count: u32,
+    common_args: &[String],
+    log: &LogSender,
+) -> SearchOutcome {
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args)
+        .args(["--flat-playlist", "-J"])
+        .arg(site.url(query, count));
+
+    let output = logs::run_logged(&mut cmd, "Search", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!("Search failed: {}", output.error_message()));
+    }
+
+    parse_results(&output.stdout.join("\n"))
+}
+
+fn parse_results(output: &str) -> SearchOutcome {
+    let json: Value = serde_json::from_str(output)
+        .map_err(|e| format!("Failed to parse yt-dlp output: {}", e))?;
+
+    Ok(json
+        .get("entries")
+        .and_then(Value::as_array)
+        .map(|entries| entries.iter().filter_map(parse_entry).collect())
+        .unwrap_or_default())
+}
+
+fn parse_entry(entry: &Value) -> Option<SearchResult> {
+    let text = |key: &str| entry.get(key).and_then(Value::as_str);
+
+    let url = text("webpage_url").or_else(|| text("url"))?.to_string();
+    let channel = text("channel")
+        .or_else(|| text("uploader"))
+        .unwrap_or_default()
+        .to_string();
+
+    // Thumbnails are listed smallest first, the first reasonably sized one is plenty for a list
+    let thumbnails = entry.get("thumbnails").and_then(Value::as_array);
+    let thumbnail = thumbnails
+        .and_then(|thumbnails| {
+            thumbnails
+                .iter()
+                .find(|t| t.get("width").and_then(Value::as_u64).unwrap_or(0) >= 160)
+                .or_else(|| thumbnails.last())
+        })
+        .and_then(|t| t.get("url").and_then(Value::as_str))
+        .or_else(|| text("thumbnail"))
+        .map(|url| url.to_string());
+
+    Some(SearchResult {
+        title: text("title").unwrap_or(&url).to_string(),
+        channel,
+        duration: entry.get("duration").and_then(Value::as_f64),
+        url,
+        thumbnail,
+    })
+}
@@ -0,0 +1,456 @@
+// settings.rs
+use crate::appearance::AppearanceSettings;
+use crate::desktop;
+use crate::download::Preset;
+use crate::encode::EncodeProfile;
+use crate::extra_args::ArgumentPreset;
+use crate::logs::Verbosity;
+use crate::shortcuts::ShortcutSettings;
+use crate::subscriptions::Subscription;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::{env, fs, path::PathBuf, process};
+
+// Service name used for passwords stored in the system keyring
+const KEYRING_SERVICE: &str = "ytdlprustygui";
+
+// Browsers supported by yt-dlp's --cookies-from-browser
+pub const BROWSERS: [&str; 9] = [
+    "brave", "chrome", "chromium", "edge", "firefox", "opera", "safari", "vivaldi", "whale",
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub auth: AuthSettings,
+    pub network: NetworkSettings,
+    pub notifications: NotificationSettings,
+    pub verbosity: Verbosity,
+    pub presets: Vec<Preset>,
+    pub argument_presets: Vec<ArgumentPreset>,
+    pub encode_profiles: Vec<EncodeProfile>,
+    pub keep_original: bool, // Keep the downloaded file next to the encoded one
+    pub subscriptions: Vec<Subscription>,
+    pub appearance: AppearanceSettings,
+    pub shortcuts: ShortcutSettings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            auth: AuthSettings::default(),
+            network: NetworkSettings::default(),
+            notifications: NotificationSettings::default(),
+            verbosity: Verbosity::default(),
+            presets: Preset::defaults(),
+            argument_presets: Vec::new(),
+            encode_profiles: EncodeProfile::defaults(),
+            keep_original: true,
+            subscriptions: Vec::new(),
+            appearance: AppearanceSettings::default(),
+            shortcuts: ShortcutSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum AuthMethod {
+    #[default]
+    None, // No credentials
+    CookiesFile,        // Netscape formatted cookies.txt
+    CookiesFromBrowser, // Read cookies straight from a browser profile
+    Login,              // Username and password
+    Netrc,              // Credentials from a .netrc file
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AuthSettings {
+    pub method: AuthMethod,
+    pub cookies_file: String,
+    pub browser: String,
+    pub browser_profile: String,
+    pub username: String,
+    // Never written to the settings file, kept in the system keyring instead
+    #[serde(skip)]
+    pub password: String,
+    pub netrc_location: String,
+    // Private netrc holding the login, rewritten only when the credentials change
+    #[serde(skip)]
+    pub login_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum IpVersion {
+    #[default]
+    Any, // Let the system decide
+    V4, // Force IPv4
+    V6, // Force IPv6
+}
+
+// Zero or empty values leave yt-dlp's own default in place
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkSettings {
+    pub proxy: String,
+    pub rate_limit: String,
+    pub retries: u32,
+    pub fragment_retries: u32,
+    pub socket_timeout: u32,
+    pub concurrent_fragments: u32,
+    pub source_address: String,
+    pub ip_version: IpVersion,
+    pub sleep_requests: f32,
+    pub sleep_interval: f32,
+    pub max_sleep_interval: f32,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum CompletionAction {
+    #[default]
+    None, // Do nothing
+    OpenFile,   // Open the downloaded file
+    OpenFolder, // Open the output directory
+    RunCommand, // Run a user command with the file path
+    #[serde(alias = "CloseApp")]
+    CloseWhenQueueDone, // Close the app once the queue has nothing left to download
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationSettings {
+    pub notify_success: bool,
+    pub notify_failure: bool,
+    pub sound: bool,
+    pub action: CompletionAction,
+    pub command: String, // {} is replaced with the file path, appended when missing
+}
+
+impl Settings {
+    // Arguments shared by every yt-dlp command the app runs
+    pub fn common_args(&self) -> Vec<String> {
+        let mut args = self.auth.args();
+        args.extend(self.network.args());
+        args
+    }
+
+    pub fn load() -> Self {
+        let mut settings: Settings = settings_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        if settings.auth.method == AuthMethod::Login {
+            settings.auth.password = load_password(&settings.auth.username).unwrap_or_default();
+        }
+
+        settings
+    }
+
+    pub fn preset(&self, name: &str) -> Option<&Preset> {
+        self.presets.iter().find(|preset| preset.name == name)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let path = settings_path().ok_or("Could not determine the config directory")?;
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+        fs::write(&path, contents).map_err(|e| format!("Failed to save settings: {}", e))?;
+
+        // The file names cookie jars and accounts, so keep it private to the user
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o600));
+        }
+
+        Ok(())
+    }
+}
+
+impl AuthSettings {
+    // Arguments passed to every yt-dlp invocation so fetching and downloading share credentials
+    pub fn args(&self) -> Vec<String> {
+        match self.method {
+            AuthMethod::None => Vec::new(),
+            AuthMethod::CookiesFile if !self.cookies_file.is_empty() => {
+                vec!["--cookies".to_string(), self.cookies_file.clone()]
+            }
+            AuthMethod::CookiesFromBrowser if !self.browser.is_empty() => {
+                let mut spec = self.browser.clone();
+                if !self.browser_profile.is_empty() {
+                    spec.push(':');
+                    spec.push_str(&self.browser_profile);
+                }
+                vec!["--cookies-from-browser".to_string(), spec]
+            }
+            // Anything on the command line can be read by other users, so the password goes
+            // through a private netrc file instead of --password
+            AuthMethod::Login => match &self.login_file {
+                Some(path) => vec![
+                    "--netrc".to_string(),
+                    "--netrc-location".to_string(),
+                    path.to_string_lossy().to_string(),
+                ],
+                None => Vec::new(),
+            },
+            AuthMethod::Netrc => {
+                let mut args = vec!["--netrc".to_string()];
+                if !self.netrc_location.is_empty() {
+                    args.push("--netrc-location".to_string());
+                    args.push(self.netrc_location.clone());
+                }
+                args
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    // Bring the login file in line with the current method and credentials
+    pub fn write_login_file(&mut self) -> Result<(), String> {
+        self.login_file = None;
+        if self.method != AuthMethod::Login || self.username.is_empty() {
+            remove_login_netrc();
+            return Ok(());
+        }
+
+        self.login_file = Some(write_login_netrc(&self.username, &self.password)?);
+        Ok(())
+    }
+}
+
+impl NetworkSettings {
+    pub fn args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        let mut push = |flag: &str, value: String| {
+            args.push(flag.to_string());
+            args.push(value);
+        };
+
+        if !self.proxy.trim().is_empty() {
+            push("--proxy", self.proxy.trim().to_string());
+        }
+        if !self.rate_limit.trim().is_empty() {
+            push("--limit-rate", self.rate_limit.trim().to_string());
+        }
+        if self.retries > 0 {
+            push("--retries", self.retries.to_string());
+        }
+        if self.fragment_retries > 0 {
+            push("--fragment-retries", self.fragment_retries.to_string());
+        }
+        if self.socket_timeout > 0 {
+            push("--socket-timeout", self.socket_timeout.to_string());
+        }
+        if self.concurrent_fragments > 1 {
+            push(
+                "--concurrent-fragments",
+                self.concurrent_fragments.to_string(),
+            );
+        }
+        if !self.source_address.trim().is_empty() {
+            push("--source-address", self.source_address.trim().to_string());
+        }
+        if self.sleep_requests > 0.0 {
+            push("--sleep-requests", self.sleep_requests.to_string());
+        }
+        if self.sleep_interval > 0.0 {
+            push("--sleep-interval", self.sleep_interval.to_string());
+
+            // yt-dlp only accepts a maximum together with a minimum
+            if self.max_sleep_interval > self.sleep_interval {
+                push("--max-sleep-interval", self.max_sleep_interval.to_string());
+            }
+        }
+
+        match self.ip_version {
+            IpVersion::Any => {}
+            IpVersion::V4 => args.push("--force-ipv4".to_string()),
+            IpVersion::V6 => args.push("--force-ipv6".to_string()),
+        }
+
+        args
+    }
+}
+
+impl NotificationSettings {
+    // Actions on the finished file are left to yt-dlp, which knows the final path
+    pub fn exec_args(&self) -> Vec<String> {
+        match self.action {
+            CompletionAction::OpenFile => {
+                vec!["--exec".to_string(), desktop::open_file_exec().to_string()]
+            }
+            CompletionAction::RunCommand if !self.command.trim().is_empty() => {
+                vec!["--exec".to_string(), self.command.trim().to_string()]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("ytdlprustygui").join("settings.json"))
+}
+
+pub fn load_password(username: &str) -> Option<String> {
+    if username.is_empty() {
+        return None;
+    }
+
+    keyring::Entry::new(KEYRING_SERVICE, username)
+        .and_then(|entry| entry.get_password())
+        .ok()
+}
+
+pub fn store_password(username: &str, password: &str) -> Result<(), String> {
+    if username.is_empty() {
+        return Err("Please enter a username first".to_string());
+    }
+
+    keyring::Entry::new(KEYRING_SERVICE, username)
+        .and_then(|entry| entry.set_password(password))
+        .map_err(|e| format!("Failed to store password in keyring: {}", e))
+}
+
+// Nothing stored under the name counts as deleted
+pub fn delete_password(username: &str) -> Result<(), String> {
+    match keyring::Entry::new(KEYRING_SERVICE, username).and_then(|entry| entry.delete_credential())
+    {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to remove password from keyring: {}", e)),
+    }
+}
+
+fn login_netrc_path() -> PathBuf {
+    env::temp_dir().join(format!("ytdlprustygui-login-{}.netrc", process::id()))
+}
+
+// netrc values are whitespace separated, quote the ones that contain spaces or quotes
+fn netrc_token(value: &str) -> String {
+    if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"' || c == '\\') {
+        format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// A netrc readable only by this user, its default entry is used for every site
+fn write_login_netrc(username: &str, password: &str) -> Result<PathBuf, String> {
+    let path = login_netrc_path();
+    let contents = format!(
+        "default login {} password {}\n",
+        netrc_token(username),
+        netrc_token(password)
+    );
+
+    // Write a new file next to it and swap it in, so one planted by someone else is never
+    // written to and a running download never reads a half written file
+    let staging = path.with_extension("netrc.new");
+    let _ = fs::remove_file(&staging);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options
+        .open(&staging)
+        .and_then(|mut file| file.write_all(contents.as_bytes()))
+        .and_then(|_| fs::rename(&staging, &path))
+        .map_err(|e| {
+            let _ = fs::remove_file(&staging);
+            format!("Failed to write login file: {}", e)
+        })?;
+    Ok(path)
+}
+
+// The login file must not outlive the app
+pub fn remove_login_netrc() {
+    let _ = fs::remove_file(login_netrc_path());
+}
+
+// Login files left behind by a run that crashed. On Linux the files of copies still running
+// are kept, elsewhere there is no cheap way to tell so every other one goes
+pub fn remove_stale_login_files() {
+    let Ok(entries) = fs::read_dir(env::temp_dir()) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let Some(pid) = name
+            .strip_prefix("ytdlprustygui-login-")
+            .and_then(|rest| rest.split('.').next())
+            .and_then(|pid| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        if pid == process::id()
+            || (cfg!(target_os = "linux") && PathBuf::from(format!("/proc/{}", pid)).exists())
+        {
+            continue;
+        }
+        let _ = fs::remove_file(entry.path());
+    }
+}
+
+// List the profile names a browser has on this machine, for the profile picker
+pub fn browser_profiles(browser: &str) -> Vec<String> {
+    let Some(home) = dirs::home_dir() else {
+        return Vec::new();
+    };
+    let config = dirs::config_dir().unwrap_or_else(|| home.join(".config"));
+
+    if browser == "firefox" {
+        let roots = [
+            home.join(".mozilla/firefox"),
+            config.join("Mozilla/Firefox"),
+            home.join("Library/Application Support/Firefox"),
+        ];
+
+        return roots
+            .iter()
+            .filter_map(|root| fs::read_to_string(root.join("profiles.ini")).ok())
+            .flat_map(|ini| {
+                ini.lines()
+                    .filter_map(|line| line.strip_prefix("Path="))
+                    .map(|path| path.trim().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+    }
+
+    // Chromium based browsers keep one directory per profile inside their user data dir
+    let user_data = match browser {
+        "brave" => config.join("BraveSoftware/Brave-Browser"),
+        "chrome" => config.join("google-chrome"),
+        "chromium" => config.join("chromium"),
+        "edge" => config.join("microsoft-edge"),
+        "opera" => config.join("opera"),
+        "vivaldi" => config.join("vivaldi"),
+        "whale" => config.join("naver-whale"),
+        _ => return Vec::new(),
+    };
+
+    let mut profiles: Vec<String> = fs::read_dir(user_data)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name == "Default" || name.starts_with("Profile "))
+                .collect()
+        })
+        .unwrap_or_default();
+    profiles.sort();
+    profiles
+}
@@ -0,0 +1,156 @@
+// shortcuts.rs
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+// Things the keyboard can do from anywhere in the window
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    PasteAndFetch,
+    Download,
+    Cancel,
+    FocusUrl,
+    BrowseOutput,
+    ShowShortcuts,
+}
+
+impl Action {
+    pub const ALL: [Action; 6] = [
+        Action::PasteAndFetch,
+        Action::Download,
+        Action::Cancel,
+        Action::FocusUrl,
+        Action::BrowseOutput,
+        Action::ShowShortcuts,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Action::PasteAndFetch => "Paste URL and fetch formats",
+            Action::Download => "Download",
+            Action::Cancel => "Cancel fetch or download",
+            Action::FocusUrl => "Focus the URL field",
+            Action::BrowseOutput => "Browse for the output directory",
+            Action::ShowShortcuts => "Show keyboard shortcuts",
+        }
+    }
+}
+
+// A key with its modifiers, stored by name so the settings file stays readable
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KeyBinding {
+    pub ctrl: bool, // Cmd on macOS
+    pub shift: bool,
+    pub alt: bool,
+    pub key: String, // egui key name, empty when unbound
+}
+
+impl KeyBinding {
+    pub fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self {
+            ctrl: modifiers.command,
+            shift: modifiers.shift,
+            alt: modifiers.alt,
+            key: key.name().to_string(),
+        }
+    }
+
+    pub fn shortcut(&self) -> Option<KeyboardShortcut> {
+        let key = Key::from_name(&self.key)?;
+        let mut modifiers = Modifiers::NONE;
+        if self.ctrl {
+            modifiers |= Modifiers::COMMAND;
+        }
+        if self.shift {
+            modifiers |= Modifiers::SHIFT;
+        }
+        if self.alt {
+            modifiers |= Modifiers::ALT;
+        }
+        Some(KeyboardShortcut::new(modifiers, key))
+    }
+
+    pub fn label(&self, ctx: &egui::Context) -> String {
+        match self.shortcut() {
+            Some(shortcut) => ctx.format_shortcut(&shortcut),
+            None => "Unbound".to_string(),
+        }
+    }
+
+    // The window system turns this into a paste event instead of a key press
+    pub fn is_paste(&self) -> bool {
+        self.ctrl && !self.alt && self.key == Key::V.name()
+    }
+
+    // Plain keys belong to the text field being typed in, function keys and chords don't
+    pub fn works_while_typing(&self) -> bool {
+        self.ctrl
+            || self.alt
+            || self
+                .key
+                .strip_prefix('F')
+                .is_some_and(|number| number.parse::<u8>().is_ok())
+    }
+
+    // Extra modifiers, bindings with more of them have to be checked first
+    pub fn specificity(&self) -> u8 {
+        self.shift as u8 + self.alt as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ShortcutSettings {
+    pub paste_and_fetch: KeyBinding,
+    pub download: KeyBinding,
+    pub cancel: KeyBinding,
+    pub focus_url: KeyBinding,
+    pub browse_output: KeyBinding,
+    pub show_shortcuts: KeyBinding,
+}
+
+impl Default for ShortcutSettings {
+    fn default() -> Self {
+        Self {
+            paste_and_fetch: KeyBinding::new(Modifiers::COMMAND, Key::V),
+            download: KeyBinding::new(Modifiers::COMMAND, Key::Enter),
+            cancel: KeyBinding::new(Modifiers::NONE, Key::Escape),
+            focus_url: KeyBinding::new(Modifiers::COMMAND, Key::L),
+            browse_output: KeyBinding::new(Modifiers::COMMAND, Key::O),
+            show_shortcuts: KeyBinding::new(Modifiers::NONE, Key::F1),
+        }
+    }
+}
+
+impl ShortcutSettings {
+    pub fn binding(&self, action: Action) -> &KeyBinding {
+        match action {
+            Action::PasteAndFetch => &self.paste_and_fetch,
+            Action::Download => &self.download,
+            Action::Cancel => &self.cancel,
+            Action::FocusUrl => &self.focus_url,
+            Action::BrowseOutput => &self.browse_output,
+            Action::ShowShortcuts => &self.show_shortcuts,
+        }
+    }
+
+    pub fn binding_mut(&mut self, action: Action) -> &mut KeyBinding {
+        match action {
+            Action::PasteAndFetch => &mut self.paste_and_fetch,
+            Action::Download => &mut self.download,
+            Action::Cancel => &mut self.cancel,
+            Action::FocusUrl => &mut self.focus_url,
+            Action::BrowseOutput => &mut self.browse_output,
+            Action::ShowShortcuts => &mut self.show_shortcuts,
+        }
+    }
+
+    // Another action bound to the same keys, only one of them would ever run
+    pub fn conflict(&self, action: Action) -> Option<Action> {
+        let binding = self.binding(action);
+        binding.shortcut()?;
+        Action::ALL
+            .into_iter()
+            .find(|other| *other != action && self.binding(*other) == binding)
+    }
+}
@@ -0,0 +1,119 @@
+// subscriptions.rs
+use crate::download::{self, DEFAULT_TEMPLATE, DownloadRequest, Preset};
+use crate::logs::{self, LogSender};
+use chrono::{Days, Local, TimeZone};
+use serde::{Deserialize, Serialize};
+
+// Archive file kept in each subscription's output directory
+const ARCHIVE_FILE: &str = ".yt-dlp-archive.txt";
+
+pub type CheckResult = Result<Vec<String>, String>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Schedule {
+    Interval { hours: u32 },          // Every N hours after the last check
+    Daily { hour: u32, minute: u32 }, // Once a day at a local time
+}
+
+// A channel or playlist that is checked for new items while the app runs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Subscription {
+    pub id: u64,
+    pub name: String,
+    pub url: String,
+    pub preset: String,
+    pub output_dir: String,
+    pub output_template: String,
+    pub schedule: Schedule,
+    pub enabled: bool,
+    pub last_checked: Option<i64>, // Unix timestamp of the last finished check
+    pub last_result: String,
+}
+
+impl Subscription {
+    pub fn new(id: u64, preset: &str, output_dir: &str) -> Self {
+        Self {
+            id,
+            name: String::new(),
+            url: String::new(),
+            preset: preset.to_string(),
+            output_dir: output_dir.to_string(),
+            output_template: DEFAULT_TEMPLATE.to_string(),
+            schedule: Schedule::Interval { hours: 24 },
+            enabled: true,
+            last_checked: None,
+            last_result: String::new(),
+        }
+    }
+
+    // When the next check should run, None if it has never been checked
+    pub fn next_check(&self) -> Option<i64> {
+        let last = self.last_checked?;
+
+        match self.schedule {
+            Schedule::Interval { hours } => Some(last + i64::from(hours.max(1)) * 3600),
+            Schedule::Daily { hour, minute } => {
+                let last_local = Local.timestamp_opt(last, 0).single()?;
+                let mut next =
+                    last_local
+                        .date_naive()
+                        .and_hms_opt(hour.min(23), minute.min(59), 0)?;
+
+                if next.and_local_timezone(Local).earliest()? <= last_local {
+                    next = next.checked_add_days(Days::new(1))?;
+                }
+
+                Some(next.and_local_timezone(Local).earliest()?.timestamp())
+            }
+        }
+    }
+
+    pub fn is_due(&self, now: i64) -> bool {
+        self.enabled && self.next_check().is_none_or(|next| now >= next)
+    }
+
+    pub fn request(&self, preset: &Preset) -> DownloadRequest {
+        DownloadRequest {
+            archive: Some(format!("{}/{}", self.output_dir, ARCHIVE_FILE)),
+            ..preset.request(&self.url, &self.output_dir, &self.output_template)
+        }
+    }
+}
+
+// Download whatever is not in the archive yet and return the titles that were fetched
+pub fn check(
+    request: &DownloadRequest,
+    common_args: &[String],
+    source: &str,
+    log: &LogSender,
+) -> CheckResult {
+    let mut cmd = request.command(common_args);
+    cmd.arg("--ignore-errors");
+
+    match logs::run_logged(&mut cmd, source, log, true) {
+        Ok(output) => {
+            let titles: Vec<String> = output
+                .stdout
+                .iter()
+                .filter_map(|line| download::parse_filepath(line))
+                .filter_map(|path| Some(path.file_stem()?.to_string_lossy().to_string()))
+                .collect();
+
+            // With --ignore-errors a single unavailable video fails the exit code
+            if output.success || !titles.is_empty() {
+                Ok(titles)
+            } else {
+                Err(format!("Check failed: {}", output.error_message()))
+            }
+        }
+        Err(e) => Err(format!("Failed to execute yt-dlp: {}", e)),
+    }
+}
+
+pub fn format_timestamp(timestamp: i64) -> String {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
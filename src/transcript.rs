@@ -0,0 +1,393 @@
+// transcript.rs
+use crate::formats::{SubtitleTrack, format_duration};
+use crate::logs::{self, LogSender};
+use serde_json::json;
+use std::path::{Path, PathBuf};
+use std::{env, fs, process::Command};
+
+pub type TranscriptResult = Result<Vec<PathBuf>, String>;
+
+// Paragraphs are split at pauses of this many seconds, or after running this long
+const PAUSE: f64 = 2.0;
+const MAX_PARAGRAPH: f64 = 60.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TranscriptFormat {
+    Plain,
+    Timestamped,
+    Markdown,
+    Json,
+}
+
+impl TranscriptFormat {
+    pub const ALL: [TranscriptFormat; 4] = [
+        TranscriptFormat::Plain,
+        TranscriptFormat::Timestamped,
+        TranscriptFormat::Markdown,
+        TranscriptFormat::Json,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Plain => "Plain text",
+            TranscriptFormat::Timestamped => "Timestamped text",
+            TranscriptFormat::Markdown => "Markdown with links",
+            TranscriptFormat::Json => "JSON segments",
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            TranscriptFormat::Plain | TranscriptFormat::Timestamped => "txt",
+            TranscriptFormat::Markdown => "md",
+            TranscriptFormat::Json => "json",
+        }
+    }
+}
+
+// Text shown between two times, in seconds
+#[derive(Debug, Clone)]
+pub struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+}
+
+// Download the subtitle track as VTT and write a transcript per video into the folder
+pub fn export(
+    url: &str,
+    track: &SubtitleTrack,
+    format: TranscriptFormat,
+    folder: &Path,
+    common_args: &[String],
+    log: &LogSender,
+) -> TranscriptResult {
+    // Subtitles go to a scratch folder, only the transcript ends up in the output directory
+    let scratch = env::temp_dir().join(format!("ytdlprustygui-transcript-{}", std::process::id()));
+    fs::create_dir_all(&scratch)
+        .map_err(|e| format!("Failed to create temporary folder: {}", e))?;
+
+    let result = download_and_convert(url, track, format, folder, &scratch, common_args, log);
+    let _ = fs::remove_dir_all(&scratch);
+    result
+}
+
+fn download_and_convert(
+    url: &str,
+    track: &SubtitleTrack,
+    format: TranscriptFormat,
+    folder: &Path,
+    scratch: &Path,
+    common_args: &[String],
+    log: &LogSender,
+) -> TranscriptResult {
+    let write_flag = if track.automatic {
+        "--write-auto-subs"
+    } else {
+        "--write-subs"
+    };
+
+    let mut cmd = Command::new("yt-dlp");
+    cmd.args(common_args)
+        .args(["--skip-download", write_flag, "--sub-format", "vtt"])
+        .args(["--sub-langs", &track.lang])
+        .arg("-o")
+        .arg(scratch.join("%(title)s [%(id)s].%(ext)s"))
+        .arg(url);
+
+    let output = logs::run_logged(&mut cmd, "Transcript", log, false)
+        .map_err(|e| format!("Failed to execute yt-dlp: {}", e))?;
+    if !output.success {
+        return Err(format!(
+            "Fetching subtitles failed: {}",
+            output.error_message()
+        ));
+    }
+
+    let mut files = Vec::new();
+    let entries = fs::read_dir(scratch).map_err(|e| e.to_string())?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "vtt") {
+            continue;
+        }
+
+        let vtt = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let segments = parse_vtt(&vtt);
+
+        // "Title [id].en.vtt" becomes "Title [id].en.md"
+        let stem = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let title = stem
+            .rsplit_once(" [")
+            .map(|(title, _)| title)
+            .unwrap_or(&stem);
+        let video_url = stem
+            .rsplit_once(" [")
+            .and_then(|(_, rest)| rest.split_once(']'))
+            .map(|(id, _)| video_link(url, id))
+            .unwrap_or_else(|| url.to_string());
+
+        let contents = match format {
+            TranscriptFormat::Plain => to_plain(&segments),
+            TranscriptFormat::Timestamped => to_timestamped(&segments),
+            TranscriptFormat::Markdown => to_markdown(&segments, title, &video_url),
+            TranscriptFormat::Json => to_json(&segments, title, &video_url)?,
+        };
+
+        let target = folder.join(format!("{}.{}", stem, format.extension()));
+        fs::write(&target, contents)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        files.push(target);
+    }
+
+    if files.is_empty() {
+        return Err(format!("No {} subtitles were found", track.label()));
+    }
+    Ok(files)
+}
+
+// Link to a single video of a playlist when the id is known
+fn video_link(url: &str, id: &str) -> String {
+    if url.contains("youtube.com") || url.contains("youtu.be") {
+        format!("https://www.youtube.com/watch?v={}", id)
+    } else {
+        url.to_string()
+    }
+}
+
+// Read WebVTT cues and collapse the rolling captions YouTube produces, where every cue repeats
+// the previous line before adding a new one
+pub fn parse_vtt(vtt: &str) -> Vec<Segment> {
+    let mut segments: Vec<Segment> = Vec::new();
+    let mut lines = vtt.lines();
+
+    while let Some(line) = lines.next() {
+        let Some((start, end)) = parse_cue_timing(line) else {
+            continue;
+        };
+
+        // YouTube starts cues with a line holding a single space, only an empty line ends one
+        for text in lines.by_ref().take_while(|line| !line.is_empty()) {
+            let text = strip_tags(text);
+            if text.is_empty() {
+                continue;
+            }
+
+            match segments.last_mut() {
+                // Same line shown again, just extend how long it was on screen
+                Some(last) if last.text == text => last.end = last.end.max(end),
+                _ => segments.push(Segment { start, end, text }),
+            }
+        }
+    }
+
+    segments
+}
+
+fn parse_cue_timing(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_time(start.trim())?, parse_time(end)?))
+}
+
+// 01:02:03.456 or 02:03.456
+fn parse_time(text: &str) -> Option<f64> {
+    text.split(':').try_fold(0.0, |total, part| {
+        Some(total * 60.0 + part.parse::<f64>().ok()?)
+    })
+}
+
+// Remove <00:00:01.500>, <c> and similar inline markup and decode the common entities
+fn strip_tags(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for c in text.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => plain.push(c),
+            _ => {}
+        }
+    }
+
+    plain
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Segments grouped into paragraphs at pauses, with the time each paragraph starts
+fn paragraphs(segments: &[Segment]) -> Vec<(f64, String)> {
+    let mut paragraphs: Vec<(f64, String)> = Vec::new();
+    let mut last_end = f64::NEG_INFINITY;
+
+    for segment in segments {
+        let continues = paragraphs.last().is_some_and(|(start, _)| {
+            segment.start - last_end < PAUSE && segment.start - start < MAX_PARAGRAPH
+        });
+
+        match paragraphs.last_mut() {
+            Some((_, text)) if continues => {
+                text.push(' ');
+                text.push_str(&segment.text);
+            }
+            _ => paragraphs.push((segment.start, segment.text.clone())),
+        }
+        last_end = segment.end;
+    }
+
+    paragraphs
+}
+
+fn to_plain(segments: &[Segment]) -> String {
+    paragraphs(segments)
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn to_timestamped(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("[{}] {}\n", format_duration(segment.start), segment.text))
+        .collect()
+}
+
+fn to_markdown(segments: &[Segment], title: &str, url: &str) -> String {
+    // Brackets in the title would end the link text early
+    let title = title
+        .replace('\\', "\\\\")
+        .replace('[', "\\[")
+        .replace(']', "\\]");
+    let mut markdown = format!("# [{}]({})\n\n", title, url);
+    let separator = if url.contains('?') { '&' } else { '?' };
+
+    for (start, text) in paragraphs(segments) {
+        markdown.push_str(&format!(
+            "[{}]({}{}t={}s) {}\n\n",
+            format_duration(start),
+            url,
+            separator,
+            start.floor(),
+            text
+        ));
+    }
+
+    markdown
+}
+
+fn to_json(segments: &[Segment], title: &str, url: &str) -> Result<String, String> {
+    let segments: Vec<_> = segments
+        .iter()
+        .map(|segment| {
+            json!({
+                "start": segment.start,
+                "end": segment.end,
+                "text": segment.text,
+            })
+        })
+        .collect();
+
+    serde_json::to_string_pretty(&json!({
+        "title": title,
+        "url": url,
+        "segments": segments,
+    }))
+    .map_err(|e| format!("Failed to serialize transcript: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Automatic captions as YouTube serves them: each cue repeats the line before it, with
+    // word timings inline, and a 10ms cue keeps the finished line on screen
+    const ROLLING_VTT: &str = "WEBVTT
+Kind: captions
+Language: en
+
+00:00:00.160 --> 00:00:02.070 align:start position:0%
+ 
+hello<00:00:00.480><c> everyone</c><00:00:00.960><c> and</c><00:00:01.280><c> welcome</c>
+
+00:00:02.070 --> 00:00:02.080 align:start position:0%
+hello everyone and welcome
+ 
+
+00:00:02.080 --> 00:00:04.310 align:start position:0%
+hello everyone and welcome
+to<00:00:02.560><c> the</c><00:00:02.800><c> show</c>
+
+00:00:04.310 --> 00:00:04.320 align:start position:0%
+to the show
+ 
+
+00:00:08.000 --> 00:00:09.500 align:start position:0%
+ 
+next<00:00:08.400><c> part</c>
+";
+
+    fn collapsed(segments: &[Segment]) -> Vec<(f64, f64, &str)> {
+        segments
+            .iter()
+            .map(|segment| (segment.start, segment.end, segment.text.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn rolling_captions_are_collapsed() {
+        let segments = parse_vtt(ROLLING_VTT);
+        assert_eq!(
+            collapsed(&segments),
+            vec![
+                (0.16, 4.31, "hello everyone and welcome"),
+                (2.08, 4.32, "to the show"),
+                (8.0, 9.5, "next part"),
+            ]
+        );
+    }
+
+    #[test]
+    fn paragraphs_split_at_pauses() {
+        let segments = parse_vtt(ROLLING_VTT);
+        assert_eq!(
+            paragraphs(&segments),
+            vec![
+                (0.16, "hello everyone and welcome to the show".to_string()),
+                (8.0, "next part".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn inline_tags_and_entities_are_removed() {
+        assert_eq!(
+            strip_tags("rock<00:00:01.500><c> &amp;</c><00:00:01.900><c>&nbsp;roll</c>"),
+            "rock & roll"
+        );
+    }
+
+    #[test]
+    fn markdown_title_brackets_are_escaped() {
+        let segments = parse_vtt(ROLLING_VTT);
+        let markdown = to_markdown(
+            &segments,
+            r"[Live] Q&A \ part 1",
+            "https://www.youtube.com/watch?v=abc",
+        );
+        assert_eq!(
+            markdown.lines().next(),
+            Some(r"# [\[Live\] Q&A \\ part 1](https://www.youtube.com/watch?v=abc)")
+        );
+        assert!(markdown.contains("[0:08](https://www.youtube.com/watch?v=abc&t=8s) next part"));
+    }
+}
@@ -0,0 +1,285 @@
+// views/channel.rs
+use crate::YtDlpGUI;
+use crate::appearance::Status;
+use crate::channel::{self, ChannelEntry, ChannelResult, ChannelSort, ChannelTab};
+use crate::formats::format_duration;
+use eframe::egui::{self, Button, ComboBox, DragValue, RichText, TextEdit};
+use std::collections::HashSet;
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+pub struct ChannelView {
+    url: String,
+    tab: ChannelTab,
+    page: u32,
+    page_size: u32,
+    sort: ChannelSort,
+    entries: Vec<ChannelEntry>,
+    selected: HashSet<String>, // URLs of the checked entries
+    newer_than: String,
+    pub receiver: Option<Receiver<ChannelResult>>,
+}
+
+impl ChannelView {
+    pub fn new() -> Self {
+        Self {
+            url: String::new(),
+            tab: ChannelTab::Videos,
+            page: 0,
+            page_size: 30,
+            sort: ChannelSort::TabOrder,
+            entries: Vec::new(),
+            selected: HashSet::new(),
+            newer_than: String::new(),
+            receiver: None,
+        }
+    }
+}
+
+impl YtDlpGUI {
+    pub fn check_channel_receiver(&mut self) {
+        if let Some(receiver) = &self.channel.receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(entries) => {
+                    self.channel.entries = entries;
+                    self.channel.sort.sort(&mut self.channel.entries);
+                }
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.channel.receiver = None;
+        }
+    }
+
+    fn fetch_channel_page(&mut self, page: u32) {
+        if self.channel.url.trim().is_empty() {
+            self.set_status("Please enter a channel URL", Status::Error);
+            return;
+        }
+
+        self.channel.page = page;
+        self.channel.entries.clear();
+        self.channel.selected.clear();
+
+        let tab_url = self.channel.tab.url(&self.channel.url);
+        let page_size = self.channel.page_size;
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.channel.receiver = Some(receiver);
+
+        thread::spawn(move || {
+            let result = channel::fetch(&tab_url, page, page_size, &common_args, &log);
+            let _ = sender.send(result);
+        });
+    }
+
+    fn queue_channel_selected(&mut self) {
+        let selected: Vec<(String, String)> = self
+            .channel
+            .entries
+            .iter()
+            .filter(|entry| self.channel.selected.contains(&entry.url))
+            .map(|entry| (entry.title.clone(), entry.url.clone()))
+            .collect();
+
+        let mut queued = 0;
+        for (title, url) in &selected {
+            if self.queue_with_preset(title, url, Vec::new()) {
+                queued += 1;
+            }
+        }
+
+        if queued > 0 {
+            self.channel.selected.clear();
+            self.set_status(&format!("Queued {} downloads", queued), Status::Info);
+        }
+    }
+
+    // One download of the whole tab, yt-dlp checks the real upload dates
+    fn queue_channel_newer_than(&mut self) {
+        let Some(date) = channel::parse_date(&self.channel.newer_than) else {
+            self.set_status("Enter the date as YYYY-MM-DD", Status::Error);
+            return;
+        };
+
+        let tab_url = self.channel.tab.url(&self.channel.url);
+        let title = format!("{} since {}", tab_url, channel::format_date(&date));
+
+        // Tabs list the newest first, so stop at the first older entry instead of checking them all
+        let extra_args = vec![
+            "--dateafter".to_string(),
+            date.clone(),
+            "--break-match-filters".to_string(),
+            format!("upload_date>={}", date),
+        ];
+
+        if self.queue_with_preset(&title, &tab_url, extra_args) {
+            self.set_status(&format!("Queued {}", title), Status::Info);
+        }
+    }
+
+    pub fn show_channel_view(&mut self, ui: &mut egui::Ui) {
+        let mut fetch_page = None;
+        let mut queue_selected = false;
+        let mut queue_newer = false;
+
+        ui.horizontal(|ui| {
+            ui.label("Channel URL:");
+            ui.add(
+                TextEdit::singleline(&mut self.channel.url)
+                    .hint_text("https://www.youtube.com/@name"),
+            );
+        });
+
+        let loading = self.channel.receiver.is_some();
+        ui.horizontal(|ui| {
+            for tab in ChannelTab::ALL {
+                if ui
+                    .selectable_label(self.channel.tab == tab, tab.label())
+                    .clicked()
+                    && self.channel.tab != tab
+                {
+                    self.channel.tab = tab;
+                    fetch_page = Some(0);
+                }
+            }
+
+            ui.separator();
+            ui.label("Per page:");
+            ui.add(DragValue::new(&mut self.channel.page_size).range(10..=200));
+
+            if ui.add_enabled(!loading, Button::new("Load")).clicked() {
+                fetch_page = Some(0);
+            }
+            if loading {
+                ui.spinner();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let previous = !loading && self.channel.page > 0;
+            if ui
+                .add_enabled(previous, Button::new("◀ Previous"))
+                .clicked()
+            {
+                fetch_page = Some(self.channel.page - 1);
+            }
+            ui.label(format!("Page {}", self.channel.page + 1));
+            // A short page is the last one
+            let next = !loading && self.channel.entries.len() as u32 >= self.channel.page_size;
+            if ui.add_enabled(next, Button::new("Next ▶")).clicked() {
+                fetch_page = Some(self.channel.page + 1);
+            }
+
+            ui.separator();
+            let sort = self.channel.sort;
+            ComboBox::new("channel_sort_combo", "")
+                .selected_text(self.channel.sort.label())
+                .show_ui(ui, |ui| {
+                    for sort in ChannelSort::ALL {
+                        ui.selectable_value(&mut self.channel.sort, sort, sort.label());
+                    }
+                });
+            // Loaded pages are sorted already, only a new choice needs sorting again
+            if self.channel.sort != sort {
+                self.channel.sort.sort(&mut self.channel.entries);
+            }
+        });
+
+        ui.separator();
+
+        egui::Grid::new("channel_entries_grid")
+            .num_columns(5)
+            .striped(true)
+            .show(ui, |ui| {
+                let all_selected = !self.channel.entries.is_empty()
+                    && self.channel.selected.len() == self.channel.entries.len();
+                let mut select_all = all_selected;
+                if ui.checkbox(&mut select_all, "").changed() {
+                    self.channel.selected = if select_all {
+                        self.channel
+                            .entries
+                            .iter()
+                            .map(|entry| entry.url.clone())
+                            .collect()
+                    } else {
+                        HashSet::new()
+                    };
+                }
+                ui.label(RichText::new("Title").strong());
+                ui.label(RichText::new("Date").strong());
+                ui.label(RichText::new("Views").strong());
+                ui.label(RichText::new("Duration").strong());
+                ui.end_row();
+
+                for entry in &self.channel.entries {
+                    let mut checked = self.channel.selected.contains(&entry.url);
+                    if ui.checkbox(&mut checked, "").changed() {
+                        if checked {
+                            self.channel.selected.insert(entry.url.clone());
+                        } else {
+                            self.channel.selected.remove(&entry.url);
+                        }
+                    }
+                    ui.label(&entry.title);
+                    ui.label(
+                        entry
+                            .upload_date
+                            .as_deref()
+                            .map(channel::format_date)
+                            .unwrap_or_default(),
+                    );
+                    ui.label(
+                        entry
+                            .view_count
+                            .map(|views| views.to_string())
+                            .unwrap_or_default(),
+                    );
+                    ui.label(entry.duration.map(format_duration).unwrap_or_default());
+                    ui.end_row();
+                }
+            });
+
+        ui.separator();
+
+        self.show_queue_preset(ui, "channel_preset_combo");
+
+        ui.horizontal(|ui| {
+            let label = format!("Download selected ({})", self.channel.selected.len());
+            if ui
+                .add_enabled(!self.channel.selected.is_empty(), Button::new(label))
+                .clicked()
+            {
+                queue_selected = true;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Download everything on this tab newer than:");
+            ui.add(
+                TextEdit::singleline(&mut self.channel.newer_than)
+                    .hint_text("YYYY-MM-DD")
+                    .desired_width(90.0),
+            );
+            if ui
+                .add_enabled(!self.channel.url.trim().is_empty(), Button::new("Queue"))
+                .clicked()
+            {
+                queue_newer = true;
+            }
+        });
+
+        if let Some(page) = fetch_page {
+            self.fetch_channel_page(page);
+        }
+        if queue_selected {
+            self.queue_channel_selected();
+        }
+        if queue_newer {
+            self.queue_channel_newer_than();
+        }
+    }
+}
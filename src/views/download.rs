@@ -0,0 +1,1436 @@
+// views/download.rs
+use crate::appearance::Status;
+use crate::comments::{self, CommentOptions, CommentSort, CommentsResult};
+use crate::config_files::{self, ConfigResult};
+use crate::download::{
+    AudioProcessing, DEFAULT_TEMPLATE, DownloadProgress, DownloadRequest, DownloadType,
+    LiveOptions, MetadataOptions,
+};
+use crate::encode::EncodeEvent;
+use crate::export::{self, ExportResult};
+use crate::extra_args::{self, ArgumentPreset};
+use crate::format_table::FormatTable;
+use crate::formats::{self, Format, LiveStatus, SubtitleTrack, format_duration, format_size};
+use crate::jobs::Job;
+use crate::live_chat::{self, LiveChatResult};
+use crate::logs::{self, ProcessId, Verbosity};
+use crate::shortcuts::Action;
+use crate::transcript::{self, TranscriptFormat, TranscriptResult};
+use crate::{DownloadReceiver, FormatReceiver, YtDlpGUI, desktop, show_audio_processing, unix_now};
+use eframe::egui::{self, Button, Color32, ComboBox, DragValue, RichText, TextEdit};
+use rfd::FileDialog;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+// The single download form with everything fetched for its URL
+pub struct DownloadView {
+    pub url: String,
+    pub output_dir: String,
+    selected_video_format: String,
+    selected_audio_format: String,
+    available_video_formats: Vec<Format>,
+    available_audio_formats: Vec<Format>,
+    video_table: FormatTable,
+    audio_table: FormatTable,
+    video_duration: Option<f64>,
+    pub live_status: LiveStatus,
+    pub release_timestamp: Option<i64>,
+    live_options: LiveOptions,
+    free_space: Option<u64>,
+    pub free_space_dir: String,
+    ignore_space_check: bool,
+    space_check_for: Option<(String, String, Option<u64>)>, // URL, directory and estimate it covers
+    download_type: DownloadType,
+    custom_format: String,
+    audio_processing: AudioProcessing,
+    album_mode: bool,
+    metadata_options: MetadataOptions,
+    csv_exact_dates: bool,
+    csv_receiver: Option<Receiver<ExportResult>>,
+    comment_options: CommentOptions,
+    comments_receiver: Option<Receiver<CommentsResult>>,
+    subtitle_tracks: Vec<SubtitleTrack>,
+    transcript_track: usize, // Index into subtitle_tracks
+    transcript_format: TranscriptFormat,
+    transcript_receiver: Option<Receiver<TranscriptResult>>,
+    live_chat_available: bool,
+    live_chat_receiver: Option<Receiver<LiveChatResult>>,
+    extra_args_text: String,
+    argument_preset_name: String,
+    command_preview: Option<String>,
+    config_location: String,
+    ignore_config: bool,
+    config_receiver: Option<Receiver<ConfigResult>>,
+    loaded_configs: Option<ConfigResult>,
+    pub encode_profile: String, // Empty when downloads are kept as they are
+    format_receiver: Option<FormatReceiver>,
+    fetching_url: String,
+    fetched_url: Option<String>, // URL the formats, live and subtitle details belong to
+    pub focus_url: bool,         // Move the keyboard focus to the URL field on the next frame
+    pub download_receiver: Option<DownloadReceiver>,
+    pub progress_receiver: Option<Receiver<DownloadProgress>>,
+    pub download_process: ProcessId,
+    pub output_files: Vec<PathBuf>, // Files written by the last download
+    pub encode_receiver: Option<Receiver<EncodeEvent>>,
+    pub encode_progress: f32,
+}
+
+impl DownloadView {
+    pub fn new(output_dir: String) -> Self {
+        Self {
+            url: String::new(),
+            output_dir,
+            selected_video_format: String::new(),
+            selected_audio_format: String::new(),
+            available_video_formats: Vec::new(),
+            available_audio_formats: Vec::new(),
+            video_table: FormatTable::new("video_format_table"),
+            audio_table: FormatTable::new("audio_format_table"),
+            video_duration: None,
+            live_status: LiveStatus::NotLive,
+            release_timestamp: None,
+            live_options: LiveOptions::default(),
+            free_space: None,
+            free_space_dir: String::new(),
+            ignore_space_check: false,
+            space_check_for: None,
+            download_type: DownloadType::VideoAudio,
+            custom_format: String::new(),
+            audio_processing: AudioProcessing::default(),
+            album_mode: false,
+            metadata_options: MetadataOptions::default(),
+            csv_exact_dates: false,
+            csv_receiver: None,
+            comment_options: CommentOptions::default(),
+            comments_receiver: None,
+            subtitle_tracks: Vec::new(),
+            transcript_track: 0,
+            transcript_format: TranscriptFormat::Plain,
+            transcript_receiver: None,
+            live_chat_available: false,
+            live_chat_receiver: None,
+            extra_args_text: String::new(),
+            argument_preset_name: String::new(),
+            command_preview: None,
+            config_location: String::new(),
+            ignore_config: false,
+            config_receiver: None,
+            loaded_configs: None,
+            encode_profile: String::new(),
+            format_receiver: None,
+            fetching_url: String::new(),
+            fetched_url: None,
+            focus_url: true,
+            download_receiver: None,
+            progress_receiver: None,
+            download_process: ProcessId::default(),
+            output_files: Vec::new(),
+            encode_receiver: None,
+            encode_progress: 0.0,
+        }
+    }
+
+    // Background work started from this view, keeps the app repainting while it runs
+    pub fn is_busy(&self) -> bool {
+        self.config_receiver.is_some()
+            || self.csv_receiver.is_some()
+            || self.comments_receiver.is_some()
+            || self.transcript_receiver.is_some()
+            || self.live_chat_receiver.is_some()
+    }
+
+    pub fn is_fetching(&self) -> bool {
+        self.format_receiver.is_some()
+    }
+
+    // Forget everything fetched for the previous URL
+    pub fn clear_fetched(&mut self) {
+        self.fetched_url = None;
+        self.available_video_formats.clear();
+        self.available_audio_formats.clear();
+        self.selected_video_format.clear();
+        self.selected_audio_format.clear();
+        self.video_duration = None;
+        self.live_status = LiveStatus::NotLive;
+        self.release_timestamp = None;
+        self.subtitle_tracks.clear();
+        self.transcript_track = 0;
+        self.live_chat_available = false;
+    }
+
+    pub fn url_id(&self) -> egui::Id {
+        egui::Id::new("download_url")
+    }
+}
+
+impl YtDlpGUI {
+    pub fn show_download_view(&mut self, ui: &mut egui::Ui) {
+        let ctx = ui.ctx().clone();
+
+        // URL input with paste button, Enter fetches the formats
+        ui.horizontal(|ui| {
+            ui.label("URL:");
+            let url_id = self.download.url_id();
+            let response = ui.add(TextEdit::singleline(&mut self.download.url).id(url_id));
+            if self.download.focus_url {
+                response.request_focus();
+                self.download.focus_url = false;
+            }
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            if ui
+                .button("Paste")
+                .on_hover_text(self.shortcut_hint(&ctx, Action::PasteAndFetch))
+                .clicked()
+            {
+                self.handle_clipboard_paste();
+            }
+
+            if submitted && !self.is_processing && !self.download.url.is_empty() {
+                self.fetch_formats();
+            }
+        });
+
+        // Output directory selection
+        ui.horizontal(|ui| {
+            ui.label("Output Directory:");
+            ui.text_edit_singleline(&mut self.download.output_dir);
+
+            if ui
+                .button("Browse")
+                .on_hover_text(self.shortcut_hint(&ctx, Action::BrowseOutput))
+                .clicked()
+            {
+                self.browse_output_dir();
+            }
+
+            if let Some(free) = self.download.free_space {
+                ui.label(format!("{} free", format_size(free)));
+            }
+        });
+
+        // Download type selection
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Download Type:");
+
+            for download_type in DownloadType::ALL {
+                let label = download_type.label();
+                ui.radio_value(&mut self.download.download_type, download_type, label);
+            }
+        });
+
+        if self.download.download_type == DownloadType::Custom {
+            ui.horizontal(|ui| {
+                ui.label("Format expression:");
+                ui.add(
+                    TextEdit::singleline(&mut self.download.custom_format)
+                        .hint_text("e.g. bv*[vcodec^=av01]+ba[acodec=opus]/b"),
+                );
+            });
+        }
+
+        if self.download.download_type == DownloadType::AudioOnly {
+            show_audio_processing(ui, &mut self.download.audio_processing);
+        }
+
+        if self.download.download_type == DownloadType::MetadataOnly {
+            self.show_metadata_options(ui);
+        }
+
+        // Comments, transcripts and chat belong to the video, whatever is being downloaded
+        if self.download.download_type == DownloadType::MetadataOnly
+            || self.download.fetched_url.is_some()
+        {
+            self.show_comments_options(ui);
+            self.show_transcript_options(ui);
+            self.show_live_chat_options(ui);
+        }
+
+        if matches!(
+            self.download.download_type,
+            DownloadType::AudioOnly | DownloadType::AudioOriginal
+        ) {
+            ui.checkbox(&mut self.download.album_mode, "Album mode")
+                .on_hover_text(
+                    "Artist/Album folder, numbered and tagged tracks, cover.jpg and an M3U playlist",
+                );
+        }
+
+        self.show_extra_args(ui);
+        self.show_encode_options(ui);
+
+        // Format fetching and selection (only shown for modes that pick formats)
+        if self.download.download_type.uses_video_format()
+            || self.download.download_type.uses_audio_format()
+        {
+            ui.horizontal(|ui| {
+                let fetch_button = ui
+                    .add_enabled(
+                        !self.is_processing && !self.download.url.is_empty(),
+                        Button::new("Fetch Formats"),
+                    )
+                    .on_hover_text("Or press Enter in the URL field");
+
+                if fetch_button.clicked() {
+                    self.fetch_formats();
+                }
+            });
+
+            // Only show format selection when formats are available
+            if self.download.download_type.uses_video_format()
+                && !self.download.available_video_formats.is_empty()
+            {
+                // Pre-merged downloads can only use formats that carry audio too
+                let progressive = self.download.download_type == DownloadType::Progressive;
+                if progressive
+                    && Self::selected_format(
+                        &self.download.available_video_formats,
+                        &self.download.selected_video_format,
+                    )
+                    .is_none_or(|format| format.acodec.is_empty())
+                    && let Some(format) = self
+                        .download
+                        .available_video_formats
+                        .iter()
+                        .find(|format| !format.acodec.is_empty())
+                {
+                    self.download.selected_video_format = format.id.clone();
+                }
+
+                // Video format selection
+                ui.horizontal(|ui| {
+                    ui.label("Video Format:");
+
+                    ComboBox::new("video_format_combo", "")
+                        .selected_text(format!(
+                            "{} ({})",
+                            self.download.selected_video_format,
+                            self.download
+                                .available_video_formats
+                                .iter()
+                                .find(|f| f.id == self.download.selected_video_format)
+                                .map(|f| f.description.as_str())
+                                .unwrap_or("")
+                        ))
+                        .show_ui(ui, |ui| {
+                            for format in &self.download.available_video_formats {
+                                if progressive && format.acodec.is_empty() {
+                                    continue;
+                                }
+
+                                ui.selectable_value(
+                                    &mut self.download.selected_video_format,
+                                    format.id.clone(),
+                                    format!(
+                                        "{} - {} ({})",
+                                        format.id, format.resolution, format.extension
+                                    ),
+                                );
+                            }
+                        });
+                });
+            }
+
+            if self.download.download_type.uses_audio_format()
+                && !self.download.available_audio_formats.is_empty()
+            {
+                // Audio format selection
+                ui.horizontal(|ui| {
+                    ui.label("Audio Format:");
+
+                    ComboBox::new("audio_format_combo", "")
+                        .selected_text(format!(
+                            "{} ({})",
+                            self.download.selected_audio_format,
+                            self.download
+                                .available_audio_formats
+                                .iter()
+                                .find(|f| f.id == self.download.selected_audio_format)
+                                .map(|f| f.description.as_str())
+                                .unwrap_or("")
+                        ))
+                        .show_ui(ui, |ui| {
+                            for format in &self.download.available_audio_formats {
+                                ui.selectable_value(
+                                    &mut self.download.selected_audio_format,
+                                    format.id.clone(),
+                                    format!("{} - {}", format.id, format.description),
+                                );
+                            }
+                        });
+                });
+            }
+        }
+
+        if self.download.live_status != LiveStatus::NotLive {
+            self.show_live_options(ui);
+        }
+
+        // Size estimate for the selected formats
+        if let Some(estimate) = self.estimated_download_size() {
+            let too_big = self.download.free_space.is_some_and(|free| estimate > free);
+            let error_color = self.status_color(Status::Error);
+
+            ui.horizontal(|ui| {
+                ui.label(format!("Estimated size: {}", format_size(estimate)));
+
+                if too_big {
+                    ui.label(
+                        RichText::new("Not enough free space in the output directory")
+                            .color(error_color),
+                    );
+                    ui.checkbox(&mut self.download.ignore_space_check, "Download anyway");
+                }
+            });
+        }
+
+        // Download button
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(!self.is_processing, Button::new("Download"))
+                .on_hover_text(self.shortcut_hint(&ctx, Action::Download))
+                .clicked()
+            {
+                self.start_download();
+            }
+
+            if self.download.is_fetching()
+                && ui
+                    .button("Cancel")
+                    .on_hover_text(self.shortcut_hint(&ctx, Action::Cancel))
+                    .clicked()
+            {
+                self.cancel_fetch();
+            }
+
+            if ui.button("Show command").clicked() {
+                self.show_command();
+            }
+        });
+
+        // Files produced by the last download
+        let mut open_error = None;
+        for file in &self.download.output_files {
+            ui.horizontal(|ui| {
+                ui.label("Saved to:");
+                ui.label(file.to_string_lossy());
+
+                if ui.button("Open").clicked()
+                    && let Err(e) = desktop::open_path(&file.to_string_lossy())
+                {
+                    open_error = Some(e);
+                }
+
+                if ui.button("Show in folder").clicked()
+                    && let Err(e) = desktop::show_in_folder(file)
+                {
+                    open_error = Some(e);
+                }
+            });
+        }
+        if let Some(e) = open_error {
+            self.set_status(&e, Status::Error);
+        }
+
+        // Format list display
+        if !self.download.available_video_formats.is_empty()
+            || !self.download.available_audio_formats.is_empty()
+        {
+            // Video formats
+            if self.download.download_type.uses_video_format()
+                && !self.download.available_video_formats.is_empty()
+            {
+                ui.collapsing("Available Video Formats", |ui| {
+                    self.download.video_table.show(
+                        ui,
+                        &self.download.available_video_formats,
+                        &mut self.download.selected_video_format,
+                    );
+                });
+            }
+
+            // Audio formats
+            if self.download.download_type.uses_audio_format()
+                && !self.download.available_audio_formats.is_empty()
+            {
+                ui.collapsing("Available Audio Formats", |ui| {
+                    self.download.audio_table.show(
+                        ui,
+                        &self.download.available_audio_formats,
+                        &mut self.download.selected_audio_format,
+                    );
+                });
+            }
+        }
+    }
+
+    pub fn check_download_receivers(&mut self) {
+        // Formats of another video would end up in the request, or a stale live status
+        if self
+            .download
+            .fetched_url
+            .as_ref()
+            .is_some_and(|url| *url != self.download.url)
+        {
+            self.download.clear_fetched();
+        }
+
+        // "Download anyway" only covers the download it was ticked for
+        let space_check = (
+            self.download.url.clone(),
+            self.download.output_dir.clone(),
+            self.estimated_download_size(),
+        );
+        if self.download.space_check_for.as_ref() != Some(&space_check) {
+            self.download.ignore_space_check = false;
+            self.download.space_check_for = Some(space_check);
+        }
+
+        if let Some(receiver) = &self.download.live_chat_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok((count, files)) => {
+                    let message = format!("Exported {} chat messages", count);
+                    self.set_status(&message, Status::Success);
+                    self.download.output_files = files;
+                }
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.download.live_chat_receiver = None;
+        }
+
+        if let Some(receiver) = &self.download.transcript_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(files) => {
+                    let message = format!("Wrote {} transcript(s)", files.len());
+                    self.set_status(&message, Status::Success);
+                    self.download.output_files = files;
+                }
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.download.transcript_receiver = None;
+        }
+
+        if let Some(receiver) = &self.download.comments_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok((count, files)) => {
+                    let message = format!("Exported {} comments", count);
+                    self.set_status(&message, Status::Success);
+                    self.download.output_files = files;
+                }
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.download.comments_receiver = None;
+        }
+
+        if let Some(receiver) = &self.download.csv_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(rows) => self.set_status(&format!("Exported {} entries", rows), Status::Success),
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.download.csv_receiver = None;
+        }
+
+        // Check format receiver
+        if let Some(receiver) = &self.download.format_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(info) => {
+                    self.download.available_video_formats = info.video_formats;
+                    self.download.available_audio_formats = info.audio_formats;
+                    self.download.video_duration = info.duration;
+                    self.download.live_status = info.live_status;
+                    self.download.release_timestamp = info.release_timestamp;
+                    self.download.subtitle_tracks = info.subtitles;
+                    self.download.live_chat_available = info.live_chat;
+                    self.download.transcript_track = 0;
+                    self.download.fetched_url = Some(self.download.fetching_url.clone());
+
+                    if !self.download.available_video_formats.is_empty() {
+                        self.download.selected_video_format =
+                            self.download.available_video_formats[0].id.clone();
+                    }
+
+                    if !self.download.available_audio_formats.is_empty() {
+                        self.download.selected_audio_format =
+                            self.download.available_audio_formats[0].id.clone();
+                    }
+
+                    if self.download.live_status == LiveStatus::Upcoming {
+                        self.set_status("The stream hasn't started yet", Status::Warning);
+                    } else if self.download.live_status == LiveStatus::Live {
+                        self.set_status("The stream is live", Status::Success);
+                    } else if !self.download.available_video_formats.is_empty()
+                        && !self.download.available_audio_formats.is_empty()
+                    {
+                        self.set_status("Formats fetched successfully", Status::Success);
+                    } else {
+                        self.set_status(
+                            "No formats available or could not distinguish audio/video formats",
+                            Status::Error,
+                        );
+                    }
+                }
+                Err(e) => {
+                    self.set_status(&e, Status::Error);
+                }
+            }
+
+            self.is_processing = false;
+            self.download.format_receiver = None;
+        }
+    }
+
+    pub fn fetch_formats(&mut self) {
+        if self.download.url.is_empty() {
+            self.set_status("Please enter a URL first", Status::Error);
+            return;
+        }
+
+        self.set_status("Fetching available formats...", Status::Working);
+        self.is_processing = true;
+
+        // Clone values for the thread
+        let url = self.download.url.clone();
+        self.download.fetching_url = url.clone();
+        let mut common_args = self.settings.common_args();
+        if self.settings.verbosity == Verbosity::Verbose {
+            common_args.push("--verbose".to_string());
+        }
+        common_args.extend(config_files::args(
+            &self.download.config_location,
+            self.download.ignore_config,
+        ));
+        let log = self.log_sender.clone();
+
+        // Create a channel to receive results
+        let (sender, receiver) = channel();
+        self.download.format_receiver = Some(receiver);
+
+        // Spawn a new thread to run yt-dlp
+        thread::spawn(move || {
+            // Run yt-dlp to get format information, only the first item of a playlist
+            let mut cmd = Command::new("yt-dlp");
+            cmd.args(&common_args).args([
+                "-J",
+                "--playlist-items",
+                "1",
+                "--ignore-no-formats-error",
+                &url,
+            ]);
+
+            match logs::run_logged(&mut cmd, "Fetch formats", &log, false) {
+                Ok(output) => {
+                    if !output.success {
+                        // The fetch may have been cancelled and nobody is listening
+                        let _ = sender.send(Err(format!(
+                            "Failed to fetch formats: {}",
+                            output.error_message()
+                        )));
+                        return;
+                    }
+
+                    let output_str = output.stdout.join("\n");
+                    let _ = sender.send(formats::parse_info_json(&output_str));
+                }
+                Err(e) => {
+                    let _ = sender.send(Err(format!("Failed to execute yt-dlp: {}", e)));
+                }
+            }
+        });
+    }
+
+    // Stop waiting for the formats, yt-dlp finishes in the background and its answer is dropped
+    pub fn cancel_fetch(&mut self) {
+        self.download.format_receiver = None;
+        self.is_processing = false;
+        self.set_status("Fetching formats cancelled", Status::Info);
+    }
+
+    // Validate the form and turn it into a request, reporting problems in the status
+    fn build_request(&mut self) -> Option<DownloadRequest> {
+        if self.download.url.is_empty() {
+            self.set_status("Please enter a URL first", Status::Error);
+            return None;
+        }
+
+        if self.download.output_dir.is_empty() {
+            self.set_status("Please select an output directory", Status::Error);
+            return None;
+        }
+
+        // Live streams can be recorded without picking formats
+        let live = self.download.live_status != LiveStatus::NotLive;
+
+        // For video+audio mode, we need both formats selected
+        if !live
+            && self.download.download_type == DownloadType::VideoAudio
+            && (self.download.selected_video_format.is_empty()
+                || self.download.selected_audio_format.is_empty()
+                || self.download.available_video_formats.is_empty()
+                || self.download.available_audio_formats.is_empty())
+        {
+            self.set_status(
+                "Please fetch and select both video and audio formats",
+                Status::Error,
+            );
+            return None;
+        }
+
+        if !live
+            && self.download.download_type == DownloadType::VideoOnly
+            && (self.download.selected_video_format.is_empty()
+                || self.download.available_video_formats.is_empty())
+        {
+            self.set_status("Please fetch and select a video format", Status::Error);
+            return None;
+        }
+
+        if !live
+            && self.download.download_type == DownloadType::Progressive
+            && Self::selected_format(
+                &self.download.available_video_formats,
+                &self.download.selected_video_format,
+            )
+            .is_none_or(|format| format.acodec.is_empty())
+        {
+            self.set_status(
+                "Please fetch and select a format that contains both video and audio",
+                Status::Error,
+            );
+            return None;
+        }
+
+        if self.download.download_type == DownloadType::Custom
+            && self.download.custom_format.trim().is_empty()
+        {
+            self.set_status("Please enter a format expression", Status::Error);
+            return None;
+        }
+
+        let extra_args = match extra_args::parse(&self.download.extra_args_text) {
+            Ok(args) => args,
+            Err(e) => {
+                self.set_status(&e, Status::Error);
+                return None;
+            }
+        };
+
+        // Prepare the download request
+        let format_spec = match self.download.download_type {
+            // Live streams often only offer pre-merged formats, yt-dlp's default picks the best
+            DownloadType::VideoAudio | DownloadType::VideoOnly | DownloadType::Progressive
+                if live
+                    && (self.download.selected_video_format.is_empty()
+                        || self.download.available_audio_formats.is_empty()) =>
+            {
+                String::new()
+            }
+            DownloadType::VideoAudio => format!(
+                "{}+{}",
+                self.download.selected_video_format, self.download.selected_audio_format
+            ),
+            DownloadType::VideoOnly | DownloadType::Progressive => {
+                self.download.selected_video_format.clone()
+            }
+            DownloadType::AudioOnly => String::new(),
+            // Without fetched formats fall back to the best audio stream
+            DownloadType::AudioOriginal if self.download.selected_audio_format.is_empty() => {
+                "bestaudio".to_string()
+            }
+            DownloadType::AudioOriginal => self.download.selected_audio_format.clone(),
+            DownloadType::Custom => self.download.custom_format.trim().to_string(),
+            DownloadType::MetadataOnly => String::new(),
+        };
+        let request = DownloadRequest {
+            url: self.download.url.clone(),
+            output_dir: self.download.output_dir.clone(),
+            output_template: DEFAULT_TEMPLATE.to_string(),
+            download_type: self.download.download_type.clone(),
+            format_spec,
+            archive: None,
+            extra_args,
+            config_location: self.download.config_location.trim().to_string(),
+            ignore_config: self.download.ignore_config,
+            audio: self.download.audio_processing.clone(),
+            live: live.then(|| self.download.live_options.clone()),
+            album: self.download.album_mode
+                && matches!(
+                    self.download.download_type,
+                    DownloadType::AudioOnly | DownloadType::AudioOriginal
+                ),
+            metadata: self.download.metadata_options.clone(),
+        };
+
+        Some(request)
+    }
+
+    pub fn start_download(&mut self) {
+        let Some(request) = self.build_request() else {
+            return;
+        };
+
+        // Refuse downloads that won't fit on the target filesystem
+        if let (Some(estimate), Some(free)) =
+            (self.estimated_download_size(), self.download.free_space)
+            && estimate > free
+            && !self.download.ignore_space_check
+        {
+            let message = format!(
+                "Not enough disk space: about {} needed, {} free",
+                format_size(estimate),
+                format_size(free)
+            );
+            self.set_status(&message, Status::Error);
+            return;
+        }
+
+        let job = Job {
+            id: self.next_job_id(),
+            request,
+            started: unix_now(),
+            files: Vec::new(),
+        };
+        self.start_job(job);
+    }
+
+    fn show_command(&mut self) {
+        if let Some(request) = self.build_request() {
+            let mut args = self.job_args();
+            args.extend(request.args());
+            self.download.command_preview = Some(extra_args::command_line(&args));
+        }
+    }
+
+    fn show_live_options(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            match (self.download.live_status, self.download.release_timestamp) {
+                (LiveStatus::Live, _) => {
+                    ui.label(RichText::new("● Live now").color(Color32::RED).strong());
+                }
+                (LiveStatus::Upcoming, Some(start)) if start > unix_now() => {
+                    let countdown = format_duration((start - unix_now()) as f64);
+                    ui.label(format!("Upcoming stream, starts in {}", countdown));
+                }
+                (LiveStatus::Upcoming, _) => {
+                    ui.label("Upcoming stream, should start any moment");
+                }
+                (LiveStatus::NotLive, _) => {}
+            }
+
+            ui.checkbox(
+                &mut self.download.live_options.from_start,
+                "Record from the start of the stream",
+            );
+            ui.horizontal(|ui| {
+                ui.checkbox(
+                    &mut self.download.live_options.wait_for_video,
+                    "Wait for the stream to start, retry every",
+                );
+                ui.add_enabled(
+                    self.download.live_options.wait_for_video,
+                    DragValue::new(&mut self.download.live_options.retry_interval)
+                        .range(5..=3600)
+                        .suffix(" s"),
+                );
+            });
+        });
+    }
+
+    fn show_encode_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Encode after download:");
+
+            let selected = if self.download.encode_profile.is_empty() {
+                "None"
+            } else {
+                self.download.encode_profile.as_str()
+            };
+            ComboBox::new("encode_profile_combo", "")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.download.encode_profile, String::new(), "None");
+                    for profile in &self.settings.encode_profiles {
+                        ui.selectable_value(
+                            &mut self.download.encode_profile,
+                            profile.name.clone(),
+                            &profile.name,
+                        );
+                    }
+                });
+
+            ui.checkbox(&mut self.settings.keep_original, "Keep original");
+
+            let can_encode = !self.is_processing
+                && !self.download.encode_profile.is_empty()
+                && !self.download.output_files.is_empty();
+            if ui
+                .add_enabled(can_encode, Button::new("Encode now"))
+                .on_hover_text("Encode the files of the last download")
+                .clicked()
+            {
+                self.encode();
+            }
+        });
+    }
+
+    fn show_extra_args(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Extra arguments:");
+            ui.add(
+                TextEdit::singleline(&mut self.download.extra_args_text)
+                    .hint_text("e.g. --embed-subs --sub-langs \"en.*\""),
+            );
+
+            ComboBox::new("argument_preset_combo", "")
+                .selected_text("Presets")
+                .show_ui(ui, |ui| {
+                    for preset in &self.settings.argument_presets {
+                        if ui.selectable_label(false, &preset.name).clicked() {
+                            self.download.extra_args_text = preset.args.clone();
+                            self.download.argument_preset_name = preset.name.clone();
+                            self.download.config_location = preset.config_location.clone();
+                            self.download.ignore_config = preset.ignore_config;
+                        }
+                    }
+                });
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Preset name:");
+            ui.text_edit_singleline(&mut self.download.argument_preset_name);
+
+            let name = self.download.argument_preset_name.trim().to_string();
+            let exists = self
+                .settings
+                .argument_presets
+                .iter()
+                .any(|preset| preset.name == name);
+
+            if ui
+                .add_enabled(!name.is_empty(), Button::new("Save preset"))
+                .clicked()
+            {
+                let preset = ArgumentPreset {
+                    name: name.clone(),
+                    args: self.download.extra_args_text.clone(),
+                    config_location: self.download.config_location.clone(),
+                    ignore_config: self.download.ignore_config,
+                };
+                match self
+                    .settings
+                    .argument_presets
+                    .iter_mut()
+                    .find(|preset| preset.name == name)
+                {
+                    Some(existing) => *existing = preset,
+                    None => self.settings.argument_presets.push(preset),
+                }
+            }
+
+            if ui
+                .add_enabled(exists, Button::new("Delete preset"))
+                .clicked()
+            {
+                self.settings
+                    .argument_presets
+                    .retain(|preset| preset.name != name);
+            }
+        });
+
+        // yt-dlp configuration files, saved with the argument preset
+        ui.horizontal(|ui| {
+            ui.label("yt-dlp config:");
+            ui.add(
+                TextEdit::singleline(&mut self.download.config_location)
+                    .hint_text("config file or folder, empty for yt-dlp's defaults"),
+            );
+
+            if ui.button("Browse").clicked()
+                && let Some(file) = FileDialog::new().pick_file()
+            {
+                self.download.config_location = file.to_string_lossy().to_string();
+            }
+
+            ui.checkbox(&mut self.download.ignore_config, "Ignore other configs");
+
+            if ui
+                .add_enabled(
+                    self.download.config_receiver.is_none(),
+                    Button::new("Show loaded configs"),
+                )
+                .clicked()
+            {
+                self.check_loaded_configs();
+            }
+        });
+
+        // Check the arguments as they are typed
+        match extra_args::parse(&self.download.extra_args_text) {
+            Ok(args) => {
+                for warning in extra_args::warnings(&args) {
+                    ui.label(RichText::new(warning).color(self.status_color(Status::Warning)));
+                }
+            }
+            Err(e) => {
+                ui.label(RichText::new(e).color(self.status_color(Status::Error)));
+            }
+        }
+    }
+
+    fn check_loaded_configs(&mut self) {
+        let mut args = self.settings.common_args();
+        args.extend(config_files::args(
+            &self.download.config_location,
+            self.download.ignore_config,
+        ));
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.download.config_receiver = Some(receiver);
+
+        thread::spawn(move || {
+            let _ = sender.send(config_files::loaded(&args, &log));
+        });
+    }
+
+    pub fn show_configs_window(&mut self, ctx: &egui::Context) {
+        if let Some(receiver) = &self.download.config_receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            self.download.loaded_configs = Some(result);
+            self.download.config_receiver = None;
+        }
+
+        let Some(result) = &self.download.loaded_configs else {
+            return;
+        };
+        let error_color = self.status_color(Status::Error);
+        let mut open = true;
+
+        egui::Window::new("yt-dlp Configuration")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| match result {
+                Ok(configs) if configs.is_empty() => {
+                    ui.label("yt-dlp doesn't load any configuration files.");
+                }
+                Ok(configs) => {
+                    ui.label("yt-dlp loads these configuration files:");
+                    for config in configs {
+                        ui.label(RichText::new(config).monospace());
+                    }
+                }
+                Err(e) => {
+                    ui.label(RichText::new(e).color(error_color));
+                }
+            });
+
+        if !open {
+            self.download.loaded_configs = None;
+        }
+    }
+
+    pub fn show_command_window(&mut self, ctx: &egui::Context) {
+        let Some(command) = self.download.command_preview.clone() else {
+            return;
+        };
+        let mut open = true;
+
+        egui::Window::new("Command")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label("The download will run:");
+                let mut text = command.clone();
+                ui.add(
+                    TextEdit::multiline(&mut text)
+                        .code_editor()
+                        .desired_width(f32::INFINITY),
+                );
+
+                if ui.button("Copy").clicked() {
+                    ctx.copy_text(command.clone());
+                }
+            });
+
+        if !open {
+            self.download.command_preview = None;
+        }
+    }
+
+    fn selected_format(formats: &[Format], id: &str) -> Option<Format> {
+        formats.iter().find(|f| f.id == id).cloned()
+    }
+
+    // Size of the selected formats, None when formats haven't been fetched or sizes are unknown
+    fn estimated_download_size(&self) -> Option<u64> {
+        match self.download.download_type {
+            DownloadType::VideoAudio => {
+                let video = Self::selected_format(
+                    &self.download.available_video_formats,
+                    &self.download.selected_video_format,
+                )?;
+                let audio = Self::selected_format(
+                    &self.download.available_audio_formats,
+                    &self.download.selected_audio_format,
+                )?;
+                Some(
+                    video.estimated_size(self.download.video_duration)?
+                        + audio.estimated_size(self.download.video_duration)?,
+                )
+            }
+            DownloadType::VideoOnly | DownloadType::Progressive => Self::selected_format(
+                &self.download.available_video_formats,
+                &self.download.selected_video_format,
+            )?
+            .estimated_size(self.download.video_duration),
+            DownloadType::AudioOriginal => Self::selected_format(
+                &self.download.available_audio_formats,
+                &self.download.selected_audio_format,
+            )?
+            .estimated_size(self.download.video_duration),
+            // There's no telling what a custom expression will pick
+            DownloadType::Custom => None,
+            DownloadType::MetadataOnly => None,
+            // yt-dlp extracts from the best audio stream
+            DownloadType::AudioOnly => self
+                .download
+                .available_audio_formats
+                .iter()
+                .max_by(|a, b| {
+                    a.bitrate
+                        .unwrap_or(0.0)
+                        .total_cmp(&b.bitrate.unwrap_or(0.0))
+                })?
+                .estimated_size(self.download.video_duration),
+        }
+    }
+
+    pub fn refresh_free_space(&mut self) {
+        if self.download.free_space_dir == self.download.output_dir {
+            return;
+        }
+
+        // The directory may not exist yet, so ask about the closest existing parent
+        self.download.free_space = Path::new(&self.download.output_dir)
+            .ancestors()
+            .find(|dir| dir.exists())
+            .and_then(|dir| fs4::available_space(dir).ok());
+        self.download.free_space_dir = self.download.output_dir.clone();
+    }
+
+    pub fn browse_output_dir(&mut self) {
+        if let Some(folder) = FileDialog::new()
+            .set_directory(&self.download.output_dir)
+            .pick_folder()
+        {
+            self.download.output_dir = folder.to_string_lossy().to_string();
+        }
+    }
+
+    pub fn handle_clipboard_paste(&mut self) {
+        if let Ok(mut clipboard) = arboard::Clipboard::new() {
+            if let Ok(text) = clipboard.get_text() {
+                self.download.url = text;
+            } else {
+                self.set_status("Failed to paste from clipboard", Status::Error);
+            }
+        } else {
+            self.set_status("Clipboard access failed", Status::Error);
+        }
+    }
+
+    fn show_metadata_options(&mut self, ui: &mut egui::Ui) {
+        let metadata = &mut self.download.metadata_options;
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut metadata.info_json, "Info JSON");
+            ui.checkbox(&mut metadata.description, "Description");
+            ui.checkbox(&mut metadata.link, "Link shortcut");
+        });
+
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut metadata.thumbnail, "Thumbnail as");
+            ui.add_enabled_ui(metadata.thumbnail, |ui| {
+                for format in ["jpg", "png"] {
+                    ui.radio_value(&mut metadata.thumbnail_format, format.to_string(), format);
+                }
+                ui.label("Width:");
+                ui.add(
+                    DragValue::new(&mut metadata.thumbnail_width)
+                        .range(0..=3840)
+                        .speed(10.0)
+                        .custom_formatter(|width, _| {
+                            if width == 0.0 {
+                                "original".to_string()
+                            } else {
+                                format!("{} px", width)
+                            }
+                        }),
+                );
+            });
+        });
+
+        ui.horizontal(|ui| {
+            let exporting = self.download.csv_receiver.is_some();
+            if ui
+                .add_enabled(
+                    !exporting && !self.download.url.is_empty(),
+                    Button::new("Export playlist CSV"),
+                )
+                .on_hover_text("Title, URL, duration, upload date and view count of every entry")
+                .clicked()
+            {
+                self.export_csv();
+            }
+            ui.checkbox(&mut self.download.csv_exact_dates, "Exact dates (slower)");
+            if exporting {
+                ui.spinner();
+            }
+        });
+    }
+
+    fn show_comments_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let exporting = self.download.comments_receiver.is_some();
+            if ui
+                .add_enabled(
+                    !exporting && !self.download.url.is_empty(),
+                    Button::new("Export comments"),
+                )
+                .on_hover_text("CSV and threaded JSON in the output directory")
+                .clicked()
+            {
+                self.export_comments();
+            }
+
+            let options = &mut self.download.comment_options;
+            let limit = |n: f64, _| {
+                if n == 0.0 {
+                    "all".to_string()
+                } else {
+                    n.to_string()
+                }
+            };
+            ui.label("Max:");
+            ui.add(
+                DragValue::new(&mut options.max_comments)
+                    .range(0..=100_000)
+                    .custom_formatter(limit),
+            );
+            ui.label("Replies per thread:");
+            ui.add(
+                DragValue::new(&mut options.max_replies)
+                    .range(0..=10_000)
+                    .custom_formatter(limit),
+            );
+            ui.radio_value(&mut options.sort, CommentSort::Top, "Top");
+            ui.radio_value(&mut options.sort, CommentSort::Newest, "Newest");
+            if exporting {
+                ui.spinner();
+            }
+        });
+    }
+
+    fn show_transcript_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Transcript:");
+
+            if self.download.subtitle_tracks.is_empty() {
+                let fetching = self.download.format_receiver.is_some();
+                if ui
+                    .add_enabled(
+                        !fetching && !self.download.url.is_empty(),
+                        Button::new("Fetch subtitle tracks"),
+                    )
+                    .clicked()
+                {
+                    self.fetch_formats();
+                }
+                return;
+            }
+
+            let selected = self
+                .download
+                .subtitle_tracks
+                .get(self.download.transcript_track)
+                .map(|track| track.label())
+                .unwrap_or_default();
+            ComboBox::new("transcript_track_combo", "")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    for (index, track) in self.download.subtitle_tracks.iter().enumerate() {
+                        ui.selectable_value(
+                            &mut self.download.transcript_track,
+                            index,
+                            track.label(),
+                        );
+                    }
+                });
+
+            ComboBox::new("transcript_format_combo", "")
+                .selected_text(self.download.transcript_format.label())
+                .show_ui(ui, |ui| {
+                    for format in TranscriptFormat::ALL {
+                        ui.selectable_value(
+                            &mut self.download.transcript_format,
+                            format,
+                            format.label(),
+                        );
+                    }
+                });
+
+            let exporting = self.download.transcript_receiver.is_some();
+            if ui
+                .add_enabled(!exporting, Button::new("Export transcript"))
+                .clicked()
+            {
+                self.export_transcript();
+            }
+            if exporting {
+                ui.spinner();
+            }
+        });
+    }
+
+    fn show_live_chat_options(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            let exporting = self.download.live_chat_receiver.is_some();
+            if ui
+                .add_enabled(
+                    !exporting && !self.download.url.is_empty(),
+                    Button::new("Export live chat"),
+                )
+                .on_hover_text("Timestamped text log and CSV of a livestream replay's chat")
+                .clicked()
+            {
+                self.export_live_chat();
+            }
+
+            if self.download.live_chat_available {
+                ui.label("Live chat replay available");
+            }
+            if exporting {
+                ui.spinner();
+            }
+        });
+    }
+
+    fn export_live_chat(&mut self) {
+        if self.download.output_dir.is_empty() {
+            self.set_status("Please select an output directory", Status::Error);
+            return;
+        }
+
+        let url = self.download.url.clone();
+        let folder = PathBuf::from(&self.download.output_dir);
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.download.live_chat_receiver = Some(receiver);
+        self.set_status("Downloading live chat...", Status::Working);
+
+        thread::spawn(move || {
+            let _ = sender.send(live_chat::export(&url, &folder, &common_args, &log));
+        });
+    }
+
+    fn export_transcript(&mut self) {
+        let Some(track) = self
+            .download
+            .subtitle_tracks
+            .get(self.download.transcript_track)
+            .cloned()
+        else {
+            return;
+        };
+
+        if self.download.output_dir.is_empty() {
+            self.set_status("Please select an output directory", Status::Error);
+            return;
+        }
+
+        let url = self.download.url.clone();
+        let format = self.download.transcript_format;
+        let folder = PathBuf::from(&self.download.output_dir);
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.download.transcript_receiver = Some(receiver);
+        self.set_status("Fetching subtitles...", Status::Working);
+
+        thread::spawn(move || {
+            let _ = sender.send(transcript::export(
+                &url,
+                &track,
+                format,
+                &folder,
+                &common_args,
+                &log,
+            ));
+        });
+    }
+
+    fn export_comments(&mut self) {
+        if self.download.output_dir.is_empty() {
+            self.set_status("Please select an output directory", Status::Error);
+            return;
+        }
+
+        let url = self.download.url.clone();
+        let options = self.download.comment_options.clone();
+        let folder = PathBuf::from(&self.download.output_dir);
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.download.comments_receiver = Some(receiver);
+        self.set_status("Fetching comments...", Status::Working);
+
+        thread::spawn(move || {
+            let _ = sender.send(comments::export(
+                &url,
+                &options,
+                &folder,
+                &common_args,
+                &log,
+            ));
+        });
+    }
+
+    fn export_csv(&mut self) {
+        let Some(path) = FileDialog::new()
+            .set_file_name("playlist.csv")
+            .add_filter("CSV", &["csv"])
+            .save_file()
+        else {
+            return;
+        };
+
+        let url = self.download.url.clone();
+        let exact = self.download.csv_exact_dates;
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.download.csv_receiver = Some(receiver);
+        self.set_status("Exporting playlist...", Status::Working);
+
+        thread::spawn(move || {
+            let _ = sender.send(export::playlist_csv(&url, exact, &path, &common_args, &log));
+        });
+    }
+}
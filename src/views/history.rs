@@ -0,0 +1,133 @@
+// views/history.rs
+use super::View;
+use crate::appearance::Status;
+use crate::history::{self, HistoryEntry};
+use crate::{YtDlpGUI, desktop, subscriptions};
+use eframe::egui::{self, Button, RichText, TextEdit};
+
+pub struct HistoryView {
+    entries: Vec<HistoryEntry>, // Oldest first
+    filter: String,
+}
+
+impl HistoryView {
+    pub fn new() -> Self {
+        Self {
+            entries: history::load(),
+            filter: String::new(),
+        }
+    }
+}
+
+impl YtDlpGUI {
+    pub fn add_history(&mut self, entry: HistoryEntry) {
+        self.history.entries.push(entry);
+        if let Err(e) = history::save(&mut self.history.entries) {
+            self.set_status(&e, Status::Error);
+        }
+    }
+
+    pub fn show_history_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(TextEdit::singleline(&mut self.history.filter).hint_text("title or URL"));
+
+            if ui
+                .add_enabled(
+                    !self.history.entries.is_empty(),
+                    Button::new("Clear history"),
+                )
+                .clicked()
+            {
+                self.history.entries.clear();
+                if let Err(e) = history::save(&mut self.history.entries) {
+                    self.set_status(&e, Status::Error);
+                }
+            }
+        });
+
+        ui.separator();
+
+        if self.history.entries.is_empty() {
+            ui.label("Finished downloads show up here.");
+            return;
+        }
+
+        let filter = self.history.filter.to_lowercase();
+        let success_color = self.status_color(Status::Success);
+        let error_color = self.status_color(Status::Error);
+        let mut download_again = None;
+        let mut remove = None;
+        let mut open_error = None;
+
+        egui::Grid::new("history_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for (index, entry) in self.history.entries.iter().enumerate().rev() {
+                    let title = entry.title();
+                    if !filter.is_empty()
+                        && !title.to_lowercase().contains(&filter)
+                        && !entry.url.to_lowercase().contains(&filter)
+                    {
+                        continue;
+                    }
+
+                    ui.label(subscriptions::format_timestamp(entry.finished));
+
+                    let color = if entry.success {
+                        success_color
+                    } else {
+                        error_color
+                    };
+                    ui.label(RichText::new(&entry.message).color(color));
+
+                    ui.label(title).on_hover_text(&entry.url);
+
+                    ui.horizontal(|ui| {
+                        if let Some(file) = entry.files.first() {
+                            if ui.button("Open").clicked()
+                                && let Err(e) = desktop::open_path(&file.to_string_lossy())
+                            {
+                                open_error = Some(e);
+                            }
+
+                            if ui.button("Show in folder").clicked()
+                                && let Err(e) = desktop::show_in_folder(file)
+                            {
+                                open_error = Some(e);
+                            }
+                        }
+
+                        if ui.button("Download again").clicked() {
+                            download_again = Some(entry.url.clone());
+                        }
+
+                        if ui.button("Remove").clicked() {
+                            remove = Some(index);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(e) = open_error {
+            self.set_status(&e, Status::Error);
+        }
+        // The form still holds the formats of the previous video, fetch the ones of this URL
+        if let Some(url) = download_again {
+            self.download.url = url;
+            self.download.clear_fetched();
+            self.view = View::Download;
+            if !self.is_processing {
+                self.fetch_formats();
+            }
+        }
+        if let Some(index) = remove {
+            self.history.entries.remove(index);
+            if let Err(e) = history::save(&mut self.history.entries) {
+                self.set_status(&e, Status::Error);
+            }
+        }
+    }
+}
@@ -0,0 +1,121 @@
+// views/library.rs
+use crate::appearance::Status;
+use crate::formats::format_size;
+use crate::library::{self, LibraryFile};
+use crate::{YtDlpGUI, desktop, subscriptions};
+use eframe::egui::{self, Button, TextEdit};
+use std::path::PathBuf;
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+// Media files in the output directory
+pub struct LibraryView {
+    files: Vec<LibraryFile>,
+    filter: String,
+    scanned_dir: Option<String>, // Directory the files were listed from
+    pub receiver: Option<Receiver<Vec<LibraryFile>>>,
+}
+
+impl LibraryView {
+    pub fn new() -> Self {
+        Self {
+            files: Vec::new(),
+            filter: String::new(),
+            scanned_dir: None,
+            receiver: None,
+        }
+    }
+}
+
+impl YtDlpGUI {
+    fn scan_library(&mut self) {
+        let dir = self.download.output_dir.clone();
+        self.library.scanned_dir = Some(dir.clone());
+
+        let (sender, receiver) = channel();
+        self.library.receiver = Some(receiver);
+
+        thread::spawn(move || {
+            let _ = sender.send(library::scan(&PathBuf::from(dir)));
+        });
+    }
+
+    pub fn check_library_receiver(&mut self) {
+        if let Some(receiver) = &self.library.receiver
+            && let Ok(files) = receiver.try_recv()
+        {
+            self.library.files = files;
+            self.library.receiver = None;
+        }
+    }
+
+    pub fn show_library_view(&mut self, ui: &mut egui::Ui) {
+        // List the output directory again whenever it changed
+        if self.library.receiver.is_none()
+            && self.library.scanned_dir.as_deref() != Some(self.download.output_dir.as_str())
+        {
+            self.scan_library();
+        }
+
+        let scanning = self.library.receiver.is_some();
+        ui.horizontal(|ui| {
+            ui.label(format!("Files in {}", self.download.output_dir));
+            if ui.add_enabled(!scanning, Button::new("Refresh")).clicked() {
+                self.scan_library();
+            }
+            if scanning {
+                ui.spinner();
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(TextEdit::singleline(&mut self.library.filter).hint_text("file name"));
+        });
+
+        ui.separator();
+
+        if !scanning && self.library.files.is_empty() {
+            ui.label("No media files in the output directory.");
+            return;
+        }
+
+        let filter = self.library.filter.to_lowercase();
+        let mut open_error = None;
+
+        egui::Grid::new("library_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for file in &self.library.files {
+                    let name = file.name();
+                    if !filter.is_empty() && !name.to_lowercase().contains(&filter) {
+                        continue;
+                    }
+
+                    ui.label(name).on_hover_text(file.path.to_string_lossy());
+                    ui.label(format_size(file.size));
+                    ui.label(subscriptions::format_timestamp(file.modified));
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Open").clicked()
+                            && let Err(e) = desktop::open_path(&file.path.to_string_lossy())
+                        {
+                            open_error = Some(e);
+                        }
+
+                        if ui.button("Show in folder").clicked()
+                            && let Err(e) = desktop::show_in_folder(&file.path)
+                        {
+                            open_error = Some(e);
+                        }
+                    });
+                    ui.end_row();
+                }
+            });
+
+        if let Some(e) = open_error {
+            self.set_status(&e, Status::Error);
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// views/log.rs
+use crate::YtDlpGUI;
+use crate::appearance::Status;
+use crate::logs::{LogLine, Verbosity};
+use eframe::egui::{self, RichText};
+use rfd::FileDialog;
+
+// Oldest lines are dropped from the log console past this
+const MAX_LOG_LINES: usize = 10_000;
+
+pub struct LogView {
+    lines: Vec<LogLine>,
+    search: String,
+}
+
+impl LogView {
+    pub fn new() -> Self {
+        Self {
+            lines: Vec::new(),
+            search: String::new(),
+        }
+    }
+
+    pub fn last(&self) -> Option<&LogLine> {
+        self.lines.last()
+    }
+}
+
+impl YtDlpGUI {
+    // Collect output from running yt-dlp processes, keeping the log bounded
+    pub fn collect_log_lines(&mut self) {
+        while let Ok(line) = self.log_receiver.try_recv() {
+            self.log.lines.push(line);
+        }
+        if self.log.lines.len() > MAX_LOG_LINES {
+            let excess = self.log.lines.len() - MAX_LOG_LINES;
+            self.log.lines.drain(..excess);
+        }
+    }
+
+    pub fn show_log_view(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Verbosity:");
+            let verbosity = &mut self.settings.verbosity;
+            ui.radio_value(verbosity, Verbosity::Quiet, "Quiet");
+            ui.radio_value(verbosity, Verbosity::Normal, "Normal");
+            ui.radio_value(verbosity, Verbosity::Verbose, "Verbose");
+
+            ui.separator();
+            ui.label("Search:");
+            ui.text_edit_singleline(&mut self.log.search);
+
+            if ui.button("Copy log").clicked() {
+                ui.ctx().copy_text(self.log_text());
+            }
+
+            if ui.button("Save log").clicked() {
+                self.save_log();
+            }
+
+            if ui.button("Clear").clicked() {
+                self.log.lines.clear();
+            }
+        });
+
+        let search = self.log.search.to_lowercase();
+        let lines: Vec<&LogLine> = self
+            .log
+            .lines
+            .iter()
+            .filter(|line| search.is_empty() || line.text.to_lowercase().contains(&search))
+            .collect();
+
+        let error_color = self.status_color(Status::Error);
+        let warning_color = self.status_color(Status::Warning);
+
+        // Only lay out the rows that are visible, the log can get long
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::both()
+            .auto_shrink([false, false])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, lines.len(), |ui, rows| {
+                for line in &lines[rows] {
+                    let mut text = RichText::new(line.to_string()).monospace();
+                    if line.is_error && line.text.starts_with("ERROR") {
+                        text = text.color(error_color);
+                    } else if line.is_error && line.text.starts_with("WARNING") {
+                        text = text.color(warning_color);
+                    }
+                    ui.label(text);
+                }
+            });
+    }
+
+    fn log_text(&self) -> String {
+        self.log
+            .lines
+            .iter()
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn save_log(&mut self) {
+        if let Some(file) = FileDialog::new().set_file_name("yt-dlp.log").save_file() {
+            match std::fs::write(&file, self.log_text()) {
+                Ok(()) => self.set_status("Log saved", Status::Success),
+                Err(e) => self.set_status(&format!("Failed to save log: {}", e), Status::Error),
+            }
+        }
+    }
+}
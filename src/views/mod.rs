@@ -0,0 +1,164 @@
+// views/mod.rs
+mod channel;
+mod download;
+mod history;
+mod library;
+mod log;
+mod queue;
+mod search;
+mod settings;
+mod shortcuts;
+mod subscriptions;
+
+pub use channel::ChannelView;
+pub use download::DownloadView;
+pub use history::HistoryView;
+pub use library::LibraryView;
+pub use log::LogView;
+pub use queue::QueueView;
+pub use search::SearchView;
+pub use settings::SettingsView;
+pub use shortcuts::ShortcutsView;
+pub use subscriptions::SubscriptionsView;
+
+use crate::formats::{format_duration, format_size};
+use crate::shortcuts::Action;
+use crate::{YtDlpGUI, unix_now};
+use eframe::egui::{self, Button, RichText};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    Download,
+    Queue,
+    History,
+    Library,
+    Search,
+    Channel,
+    Subscriptions,
+    Log,
+    Settings,
+}
+
+impl View {
+    pub const ALL: [View; 9] = [
+        View::Download,
+        View::Queue,
+        View::History,
+        View::Library,
+        View::Search,
+        View::Channel,
+        View::Subscriptions,
+        View::Log,
+        View::Settings,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            View::Download => "Download",
+            View::Queue => "Queue",
+            View::History => "History",
+            View::Library => "Library",
+            View::Search => "Search",
+            View::Channel => "Channel",
+            View::Subscriptions => "Subscriptions",
+            View::Log => "Log",
+            View::Settings => "Settings",
+        }
+    }
+}
+
+impl YtDlpGUI {
+    pub fn show_view_tabs(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
+            ui.heading("YT-DLP Rusty GUI");
+            ui.separator();
+
+            for view in View::ALL {
+                // Show how much is waiting so the queue isn't forgotten on another tab
+                let label = match view {
+                    View::Queue if !self.queue.items.is_empty() => {
+                        format!("{} ({})", view.label(), self.queue.items.len())
+                    }
+                    _ => view.label().to_string(),
+                };
+                ui.selectable_value(&mut self.view, view, label);
+            }
+
+            ui.separator();
+            let hint = self.shortcut_hint(ui.ctx(), Action::ShowShortcuts);
+            if ui.button("Shortcuts").on_hover_text(hint).clicked() {
+                self.shortcuts.open = !self.shortcuts.open;
+            }
+        });
+    }
+
+    pub fn show_current_view(&mut self, ui: &mut egui::Ui) {
+        // The log scrolls by itself and only lays out the visible lines
+        if self.view == View::Log {
+            self.show_log_view(ui);
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .auto_shrink([false, false])
+            .show(ui, |ui| match self.view {
+                View::Download => self.show_download_view(ui),
+                View::Queue => self.show_queue_view(ui),
+                View::History => self.show_history_view(ui),
+                View::Library => self.show_library_view(ui),
+                View::Search => self.show_search_view(ui),
+                View::Channel => self.show_channel_view(ui),
+                View::Subscriptions => self.show_subscriptions_view(ui),
+                View::Log => {}
+                View::Settings => self.show_settings_view(ui),
+            });
+    }
+
+    // Status and progress, visible from every view
+    pub fn show_status_bar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Status: ");
+            ui.label(RichText::new(&self.status_message).color(self.status_color(self.status)));
+        });
+
+        if self.download.encode_receiver.is_some() {
+            ui.add(egui::ProgressBar::new(self.download.encode_progress).show_percentage());
+        } else if self.is_recording() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                match self.recording_since {
+                    Some(since) => ui.label(format!(
+                        "Recording for {}, {} so far",
+                        format_duration((unix_now() - since) as f64),
+                        format_size(self.downloaded_bytes)
+                    )),
+                    None => match self.download.release_timestamp {
+                        Some(start) if start > unix_now() => ui.label(format!(
+                            "Waiting for the stream, starts in {}",
+                            format_duration((start - unix_now()) as f64)
+                        )),
+                        _ => ui.label("Waiting for the stream to start..."),
+                    },
+                };
+
+                if ui
+                    .add_enabled(!self.stop_requested, Button::new("Stop recording"))
+                    .clicked()
+                {
+                    self.stop_recording();
+                }
+            });
+        } else if self.download.download_receiver.is_some() && self.download_progress > 0.0 {
+            ui.add(egui::ProgressBar::new(self.download_progress).show_percentage());
+        } else if self.is_processing {
+            ui.spinner();
+        }
+
+        // The latest log line, so progress is visible without opening the log
+        if self.view != View::Log
+            && let Some(line) = self.log.last()
+        {
+            ui.add(egui::Label::new(RichText::new(line.to_string()).weak()).truncate());
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// views/queue.rs
+use crate::appearance::Status;
+use crate::download::{DEFAULT_TEMPLATE, QueuedDownload};
+use crate::jobs::{self, Job};
+use crate::{YtDlpGUI, subscriptions, unix_now};
+use eframe::egui::{self, Button, ComboBox, RichText};
+use std::collections::VecDeque;
+
+// Downloads waiting for the current one
+pub struct QueueView {
+    pub items: VecDeque<QueuedDownload>,
+    preset: String, // Preset used for search and channel entries added to the queue
+    pub current_job: Option<Job>,
+    pub interrupted_jobs: Vec<Job>, // Left unfinished by a cancel or an earlier run
+}
+
+impl QueueView {
+    pub fn new(preset: String, interrupted_jobs: Vec<Job>) -> Self {
+        Self {
+            items: VecDeque::new(),
+            preset,
+            current_job: None,
+            interrupted_jobs,
+        }
+    }
+}
+
+impl YtDlpGUI {
+    // Start the next queued download once nothing else is running
+    pub fn start_next_download(&mut self) {
+        if self.is_processing || self.queue.current_job.is_some() {
+            return;
+        }
+
+        if let Some(queued) = self.queue.items.pop_front() {
+            let job = Job {
+                id: self.next_job_id(),
+                request: queued.request,
+                started: unix_now(),
+                files: Vec::new(),
+            };
+            self.start_job(job);
+            self.set_status(&format!("Downloading {}...", queued.title), Status::Working);
+        }
+    }
+
+    // Queue a request built from the preset picked for queued downloads
+    pub fn queue_with_preset(&mut self, title: &str, url: &str, extra_args: Vec<String>) -> bool {
+        let Some(preset) = self.settings.preset(&self.queue.preset) else {
+            self.set_status("Select a preset for queued downloads", Status::Error);
+            return false;
+        };
+
+        if self.download.output_dir.is_empty() {
+            self.set_status("Please select an output directory", Status::Error);
+            return false;
+        }
+
+        let mut request = preset.request(url, &self.download.output_dir, DEFAULT_TEMPLATE);
+        request.extra_args = extra_args;
+        self.queue.items.push_back(QueuedDownload {
+            title: title.to_string(),
+            request,
+        });
+        true
+    }
+
+    pub fn show_queue_preset(&mut self, ui: &mut egui::Ui, id: &str) {
+        ui.horizontal(|ui| {
+            ui.label("Queue with preset:");
+            ComboBox::new(id, "")
+                .selected_text(&self.queue.preset)
+                .show_ui(ui, |ui| {
+                    for preset in &self.settings.presets {
+                        ui.selectable_value(
+                            &mut self.queue.preset,
+                            preset.name.clone(),
+                            &preset.name,
+                        );
+                    }
+                });
+        });
+    }
+
+    fn resume_job(&mut self, id: u64) {
+        if let Some(index) = self
+            .queue
+            .interrupted_jobs
+            .iter()
+            .position(|job| job.id == id)
+        {
+            let job = self.queue.interrupted_jobs.remove(index);
+            self.start_job(job);
+        }
+    }
+
+    fn discard_job(&mut self, id: u64) {
+        if let Some(index) = self
+            .queue
+            .interrupted_jobs
+            .iter()
+            .position(|job| job.id == id)
+        {
+            let job = self.queue.interrupted_jobs.remove(index);
+            let removed = jobs::remove_partial_files(&job);
+            self.save_jobs();
+
+            let message = format!("Discarded download, removed {} partial files", removed);
+            self.set_status(&message, Status::Info);
+        }
+    }
+
+    pub fn show_queue_view(&mut self, ui: &mut egui::Ui) {
+        ui.label(RichText::new("Current download").strong());
+        match &self.queue.current_job {
+            Some(job) => {
+                ui.label(&job.request.url);
+                if self.download.download_receiver.is_some() && self.download_progress > 0.0 {
+                    ui.add(egui::ProgressBar::new(self.download_progress).show_percentage());
+                }
+            }
+            None => {
+                ui.label("Nothing is downloading");
+            }
+        }
+
+        ui.separator();
+
+        // Downloads waiting for the current one
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("Queued ({})", self.queue.items.len())).strong());
+            if ui
+                .add_enabled(!self.queue.items.is_empty(), Button::new("Clear queue"))
+                .clicked()
+            {
+                self.queue.items.clear();
+            }
+        });
+
+        let mut remove = None;
+        let mut move_up = None;
+        let last = self.queue.items.len().saturating_sub(1);
+        for (index, queued) in self.queue.items.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.add_enabled(index > 0, Button::new("⏶")).clicked() {
+                    move_up = Some(index);
+                }
+                if ui.add_enabled(index < last, Button::new("⏷")).clicked() {
+                    move_up = Some(index + 1);
+                }
+                if ui.button("Remove").clicked() {
+                    remove = Some(index);
+                }
+                ui.label(&queued.title);
+            });
+        }
+
+        if let Some(index) = move_up {
+            self.queue.items.swap(index - 1, index);
+        }
+        if let Some(index) = remove {
+            self.queue.items.remove(index);
+        }
+
+        // Downloads that were cancelled or cut off when the app last closed
+        if !self.queue.interrupted_jobs.is_empty() {
+            let mut resume = None;
+            let mut discard = None;
+
+            ui.separator();
+            ui.label(RichText::new("Interrupted downloads").strong());
+            for job in &self.queue.interrupted_jobs {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!self.is_processing, Button::new("Resume"))
+                        .clicked()
+                    {
+                        resume = Some(job.id);
+                    }
+
+                    if ui.button("Discard").clicked() {
+                        discard = Some(job.id);
+                    }
+
+                    ui.label(subscriptions::format_timestamp(job.started));
+                    ui.label(&job.request.url);
+                });
+            }
+
+            if let Some(id) = resume {
+                self.resume_job(id);
+            }
+            if let Some(id) = discard {
+                self.discard_job(id);
+            }
+        }
+    }
+}
@@ -0,0 +1,151 @@
+// views/search.rs
+use super::View;
+use crate::YtDlpGUI;
+use crate::appearance::Status;
+use crate::formats::format_duration;
+use crate::search::{self, SearchOutcome, SearchResult, SearchSite};
+use eframe::egui::{self, Button, ComboBox, DragValue, RichText, TextEdit, Vec2};
+use std::sync::mpsc::{Receiver, channel};
+use std::thread;
+
+pub struct SearchView {
+    query: String,
+    site: SearchSite,
+    count: u32,
+    results: Vec<SearchResult>,
+    pub receiver: Option<Receiver<SearchOutcome>>,
+}
+
+impl SearchView {
+    pub fn new() -> Self {
+        Self {
+            query: String::new(),
+            site: SearchSite::YouTube,
+            count: 10,
+            results: Vec::new(),
+            receiver: None,
+        }
+    }
+}
+
+impl YtDlpGUI {
+    fn start_search(&mut self) {
+        let query = self.search.query.trim().to_string();
+        if query.is_empty() {
+            return;
+        }
+
+        let site = self.search.site;
+        let count = self.search.count;
+        let common_args = self.settings.common_args();
+        let log = self.log_sender.clone();
+
+        let (sender, receiver) = channel();
+        self.search.receiver = Some(receiver);
+        self.search.results.clear();
+
+        thread::spawn(move || {
+            let _ = sender.send(search::search(site, &query, count, &common_args, &log));
+        });
+    }
+
+    fn queue_search_result(&mut self, result: &SearchResult) {
+        if self.queue_with_preset(&result.title, &result.url, Vec::new()) {
+            self.set_status(&format!("Queued {}", result.title), Status::Info);
+        }
+    }
+
+    pub fn check_search_receiver(&mut self) {
+        if let Some(receiver) = &self.search.receiver
+            && let Ok(result) = receiver.try_recv()
+        {
+            match result {
+                Ok(results) if results.is_empty() => {
+                    self.set_status("The search found nothing", Status::Info)
+                }
+                Ok(results) => self.search.results = results,
+                Err(e) => self.set_status(&e, Status::Error),
+            }
+            self.search.receiver = None;
+        }
+    }
+
+    pub fn show_search_view(&mut self, ui: &mut egui::Ui) {
+        let mut queue = None;
+        let mut use_url = None;
+
+        ui.horizontal(|ui| {
+            ComboBox::new("search_site_combo", "")
+                .selected_text(self.search.site.label())
+                .show_ui(ui, |ui| {
+                    for site in SearchSite::ALL {
+                        ui.selectable_value(&mut self.search.site, site, site.label());
+                    }
+                });
+
+            let response =
+                ui.add(TextEdit::singleline(&mut self.search.query).hint_text("Artist, title..."));
+            let submitted = response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+            ui.add(DragValue::new(&mut self.search.count).range(1..=50))
+                .on_hover_text("Number of results");
+
+            let searching = self.search.receiver.is_some();
+            if (ui.add_enabled(!searching, Button::new("Search")).clicked() || submitted)
+                && !searching
+            {
+                self.start_search();
+            }
+            if searching {
+                ui.spinner();
+            }
+        });
+
+        self.show_queue_preset(ui, "search_preset_combo");
+
+        ui.separator();
+
+        for (index, result) in self.search.results.iter().enumerate() {
+            ui.horizontal(|ui| {
+                match &result.thumbnail {
+                    Some(thumbnail) => {
+                        ui.add(
+                            egui::Image::new(thumbnail.as_str())
+                                .fit_to_exact_size(Vec2::new(120.0, 68.0)),
+                        );
+                    }
+                    None => {
+                        ui.allocate_space(Vec2::new(120.0, 68.0));
+                    }
+                }
+
+                ui.vertical(|ui| {
+                    ui.label(RichText::new(&result.title).strong());
+                    ui.label(&result.channel);
+                    if let Some(duration) = result.duration {
+                        ui.label(format_duration(duration));
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Add to queue").clicked() {
+                            queue = Some(index);
+                        }
+                        if ui.button("Use URL").clicked() {
+                            use_url = Some(index);
+                        }
+                    });
+                });
+            });
+            ui.separator();
+        }
+
+        if let Some(index) = queue {
+            let result = self.search.results[index].clone();
+            self.queue_search_result(&result);
+        }
+        if let Some(index) = use_url {
+            self.download.url = self.search.results[index].url.clone();
+            self.view = View::Download;
+        }
+    }
+}
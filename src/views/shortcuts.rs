@@ -0,0 +1,260 @@
+// views/shortcuts.rs
+use super::View;
+use crate::YtDlpGUI;
+use crate::appearance::Status;
+use crate::shortcuts::{Action, KeyBinding, ShortcutSettings};
+use eframe::egui::{self, Event, Key, Modifiers, RichText};
+use std::cmp::Reverse;
+
+// The shortcut reference and the binding being recorded in the settings
+pub struct ShortcutsView {
+    pub open: bool,
+    capture: Option<Action>, // Waiting for the keys of this action
+}
+
+impl ShortcutsView {
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            capture: None,
+        }
+    }
+}
+
+impl YtDlpGUI {
+    // Run the actions whose keys were pressed this frame, before any widget sees them
+    pub fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Recording only happens while the settings are on screen
+        if self.view != View::Settings {
+            self.shortcuts.capture = None;
+        }
+        if self.shortcuts.capture.is_some() {
+            return;
+        }
+
+        let typing = ctx.wants_keyboard_input();
+        let url_focused = ctx.memory(|memory| memory.has_focus(self.download.url_id()));
+
+        let mut actions = Action::ALL.to_vec();
+        actions
+            .sort_by_key(|action| Reverse(self.settings.shortcuts.binding(*action).specificity()));
+
+        for action in actions {
+            let binding = self.settings.shortcuts.binding(action).clone();
+            let Some(shortcut) = binding.shortcut() else {
+                continue;
+            };
+
+            // Pasting into another text field stays a normal paste
+            if action == Action::PasteAndFetch && binding.is_paste() && (!typing || url_focused) {
+                let pasted = ctx.input_mut(|input| {
+                    let text = input.events.iter().find_map(|event| match event {
+                        Event::Paste(text) => Some(text.clone()),
+                        _ => None,
+                    });
+                    input
+                        .events
+                        .retain(|event| !matches!(event, Event::Paste(_)));
+                    text
+                });
+                if let Some(text) = pasted {
+                    self.paste_and_fetch(text);
+                    continue;
+                }
+            }
+
+            if typing && !binding.works_while_typing() {
+                continue;
+            }
+
+            if ctx.input_mut(|input| input.consume_shortcut(&shortcut)) {
+                self.run_shortcut(action);
+            }
+        }
+    }
+
+    fn run_shortcut(&mut self, action: Action) {
+        match action {
+            Action::PasteAndFetch => {
+                self.handle_clipboard_paste();
+                self.paste_and_fetch(self.download.url.clone());
+            }
+            Action::Download => {
+                self.view = View::Download;
+                if !self.is_processing {
+                    self.start_download();
+                }
+            }
+            Action::Cancel => self.cancel(),
+            Action::FocusUrl => {
+                self.view = View::Download;
+                self.download.focus_url = true;
+            }
+            Action::BrowseOutput => {
+                self.view = View::Download;
+                self.browse_output_dir();
+            }
+            Action::ShowShortcuts => self.shortcuts.open = !self.shortcuts.open,
+        }
+    }
+
+    fn paste_and_fetch(&mut self, text: String) {
+        self.view = View::Download;
+        self.download.url = text.trim().to_string();
+        if !self.is_processing && !self.download.url.is_empty() {
+            self.fetch_formats();
+        }
+    }
+
+    // Close the shortcut reference first, then stop whatever is running
+    fn cancel(&mut self) {
+        if self.shortcuts.open {
+            self.shortcuts.open = false;
+        } else if self.download.is_fetching() {
+            self.cancel_fetch();
+        } else if self.is_recording() {
+            self.stop_recording();
+        } else if self.download.download_receiver.is_some() {
+            self.cancel_download();
+        }
+    }
+
+    // Text for hovering the button an action stands in for
+    pub fn shortcut_hint(&self, ctx: &egui::Context, action: Action) -> String {
+        format!(
+            "{}: {}",
+            action.label(),
+            self.settings.shortcuts.binding(action).label(ctx)
+        )
+    }
+
+    pub fn show_shortcuts_window(&mut self, ctx: &egui::Context) {
+        let mut open = self.shortcuts.open;
+        let mut edit = false;
+
+        egui::Window::new("Keyboard shortcuts")
+            .open(&mut open)
+            .resizable(false)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts_reference_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for action in Action::ALL {
+                            ui.label(action.label());
+                            ui.label(
+                                RichText::new(self.settings.shortcuts.binding(action).label(ctx))
+                                    .monospace(),
+                            );
+                            ui.end_row();
+                        }
+                    });
+
+                ui.separator();
+                ui.label("Tab and Shift+Tab move between fields and buttons.");
+                ui.label("Space or Enter presses the focused button.");
+                ui.label("Enter in the URL field fetches formats.");
+
+                if ui.button("Change shortcuts").clicked() {
+                    edit = true;
+                }
+            });
+
+        self.shortcuts.open = open && !edit;
+        if edit {
+            self.view = View::Settings;
+        }
+    }
+
+    pub fn show_shortcut_settings(&mut self, ui: &mut egui::Ui) {
+        self.record_binding(ui);
+
+        let warning_color = self.status_color(Status::Warning);
+        let ctx = ui.ctx().clone();
+
+        egui::Grid::new("shortcut_settings_grid")
+            .num_columns(4)
+            .striped(true)
+            .show(ui, |ui| {
+                for action in Action::ALL {
+                    ui.label(action.label());
+
+                    let recording = self.shortcuts.capture == Some(action);
+                    let text = if recording {
+                        "Press keys...".to_string()
+                    } else {
+                        self.settings.shortcuts.binding(action).label(&ctx)
+                    };
+                    if ui
+                        .selectable_label(recording, text)
+                        .on_hover_text("Click, then press the new keys. Esc cancels.")
+                        .clicked()
+                    {
+                        self.shortcuts.capture = if recording { None } else { Some(action) };
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Default").clicked() {
+                            *self.settings.shortcuts.binding_mut(action) =
+                                ShortcutSettings::default().binding(action).clone();
+                        }
+                        if ui.button("Clear").clicked() {
+                            *self.settings.shortcuts.binding_mut(action) = KeyBinding::default();
+                        }
+                    });
+
+                    match self.settings.shortcuts.conflict(action) {
+                        Some(other) => ui.label(
+                            RichText::new(format!("Also bound to \"{}\"", other.label()))
+                                .color(warning_color),
+                        ),
+                        None => ui.label(""),
+                    };
+                    ui.end_row();
+                }
+            });
+
+        ui.label("Keys without Ctrl or Alt are ignored while typing in a text field.");
+        if ui.button("Restore defaults").clicked() {
+            self.settings.shortcuts = ShortcutSettings::default();
+            self.shortcuts.capture = None;
+        }
+    }
+
+    // Take the next key press as the binding being recorded
+    fn record_binding(&mut self, ui: &mut egui::Ui) {
+        let Some(action) = self.shortcuts.capture else {
+            return;
+        };
+
+        let pressed = ui.input(|input| {
+            input.events.iter().find_map(|event| match event {
+                Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } if *key != Key::Tab => Some((*modifiers, *key)),
+                // Ctrl+V never arrives as a key press
+                Event::Paste(_) => Some((Modifiers::COMMAND, Key::V)),
+                _ => None,
+            })
+        });
+        let Some((modifiers, key)) = pressed else {
+            return;
+        };
+
+        // Keep the keys away from the focused button so it doesn't start recording again
+        ui.input_mut(|input| {
+            input.events.retain(|event| {
+                !matches!(event, Event::Key { pressed: true, .. } | Event::Paste(_))
+            })
+        });
+
+        if key != Key::Escape || !modifiers.is_none() {
+            *self.settings.shortcuts.binding_mut(action) = KeyBinding::new(modifiers, key);
+        }
+        self.shortcuts.capture = None;
+    }
+}
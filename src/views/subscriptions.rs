@@ -0,0 +1,311 @@
+// views/subscriptions.rs
+use crate::appearance::Status;
+use crate::subscriptions::{self, CheckResult, Schedule, Subscription};
+use crate::{YtDlpGUI, next_subscription_id, unix_now};
+use eframe::egui::{self, Button, ComboBox, DragValue, RichText};
+use rfd::FileDialog;
+use std::collections::HashMap;
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::thread;
+
+// Failed checks are tried again this many times before waiting for the next scheduled one
+const MAX_RETRIES: u32 = 3;
+// Wait before the first retry, doubled for each one after it
+const RETRY_DELAY_SECS: i64 = 60;
+
+pub struct SubscriptionsView {
+    new_subscription: Subscription,
+    running: Option<(u64, Receiver<CheckResult>)>, // Only one check runs at a time
+    retries: HashMap<u64, (u32, i64)>,             // Failed attempts and when to try again
+}
+
+impl SubscriptionsView {
+    pub fn new(new_subscription: Subscription) -> Self {
+        Self {
+            new_subscription,
+            running: None,
+            retries: HashMap::new(),
+        }
+    }
+
+    pub fn is_checking(&self) -> bool {
+        self.running.is_some()
+    }
+
+    fn is_running(&self, id: u64) -> bool {
+        self.running
+            .as_ref()
+            .is_some_and(|(running, _)| *running == id)
+    }
+
+    fn is_due(&self, subscription: &Subscription, now: i64) -> bool {
+        match self.retries.get(&subscription.id) {
+            Some((_, retry_at)) => subscription.enabled && now >= *retry_at,
+            None => subscription.is_due(now),
+        }
+    }
+}
+
+impl YtDlpGUI {
+    pub fn check_subscriptions(&mut self) {
+        self.start_subscription_check();
+
+        let Some((id, receiver)) = &self.subscriptions.running else {
+            return;
+        };
+        let id = *id;
+        let result = match receiver.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => return,
+            Err(TryRecvError::Disconnected) => Err("Check stopped unexpectedly".to_string()),
+        };
+        self.subscriptions.running = None;
+
+        let Some(subscription) = self.settings.subscriptions.iter_mut().find(|s| s.id == id) else {
+            return;
+        };
+        let summary = format!("Subscription '{}'", subscription.name);
+
+        // Give a failed check a few more tries before it waits for its next turn
+        if let Err(e) = &result {
+            let attempts = self.subscriptions.retries.get(&id).map_or(0, |(n, _)| *n) + 1;
+            if attempts <= MAX_RETRIES {
+                let delay = RETRY_DELAY_SECS << (attempts - 1);
+                self.subscriptions
+                    .retries
+                    .insert(id, (attempts, unix_now() + delay));
+                subscription.last_result = format!(
+                    "{} (retry {} of {} in {} min)",
+                    e,
+                    attempts,
+                    MAX_RETRIES,
+                    delay / 60
+                );
+                let message = format!("{}: {}", summary, subscription.last_result);
+                self.set_status(&message, Status::Warning);
+                return;
+            }
+        }
+        self.subscriptions.retries.remove(&id);
+        subscription.last_checked = Some(unix_now());
+
+        let (message, status, notify) = match result {
+            Ok(titles) if titles.is_empty() => ("No new items".to_string(), Status::Info, None),
+            Ok(titles) => (
+                format!("Downloaded {} new: {}", titles.len(), titles.join(", ")),
+                Status::Success,
+                Some(true),
+            ),
+            Err(e) => (e, Status::Error, Some(false)),
+        };
+        subscription.last_result = message.clone();
+
+        if let Some(success) = notify {
+            self.notify(&summary, &message, success);
+        }
+        self.set_status(&format!("{}: {}", summary, message), status);
+    }
+
+    // Start the first due check, the others wait until it has finished
+    fn start_subscription_check(&mut self) {
+        if self.subscriptions.is_checking() {
+            return;
+        }
+
+        let now = unix_now();
+        let Some(subscription) = self
+            .settings
+            .subscriptions
+            .iter()
+            .find(|subscription| self.subscriptions.is_due(subscription, now))
+        else {
+            return;
+        };
+
+        let (sender, receiver) = channel();
+        self.subscriptions.running = Some((subscription.id, receiver));
+
+        let request = self
+            .settings
+            .preset(&subscription.preset)
+            .map(|preset| subscription.request(preset));
+        let preset_name = subscription.preset.clone();
+        let mut common_args = self.settings.common_args();
+        common_args.extend(self.settings.verbosity.args());
+        let source = format!("Subscription '{}'", subscription.name);
+        let log = self.log_sender.clone();
+
+        thread::spawn(move || {
+            let result = match request {
+                Some(request) => subscriptions::check(&request, &common_args, &source, &log),
+                None => Err(format!("Preset '{}' not found", preset_name)),
+            };
+            let _ = sender.send(result);
+        });
+    }
+
+    pub fn show_subscriptions_view(&mut self, ui: &mut egui::Ui) {
+        let preset_names: Vec<String> = self
+            .settings
+            .presets
+            .iter()
+            .map(|p| p.name.clone())
+            .collect();
+
+        let mut remove = None;
+        let mut check_now = None;
+
+        for subscription in &mut self.settings.subscriptions {
+            let running = self.subscriptions.is_running(subscription.id);
+            let retry_at = self
+                .subscriptions
+                .retries
+                .get(&subscription.id)
+                .map(|(_, at)| *at);
+            let title = if running {
+                format!("{} (checking...)", subscription.name)
+            } else {
+                subscription.name.clone()
+            };
+
+            egui::CollapsingHeader::new(title)
+                .id_salt(subscription.id)
+                .show(ui, |ui| {
+                    show_subscription_form(ui, subscription, &preset_names);
+
+                    ui.horizontal(|ui| {
+                        ui.label("Last checked:");
+                        ui.label(
+                            subscription
+                                .last_checked
+                                .map(subscriptions::format_timestamp)
+                                .unwrap_or_else(|| "never".to_string()),
+                        );
+                        ui.label("Next check:");
+                        ui.label(
+                            retry_at
+                                .or(subscription.next_check())
+                                .map(subscriptions::format_timestamp)
+                                .unwrap_or_else(|| "now".to_string()),
+                        );
+                    });
+
+                    if !subscription.last_result.is_empty() {
+                        ui.label(&subscription.last_result);
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.add_enabled(!running, Button::new("Check now")).clicked() {
+                            check_now = Some(subscription.id);
+                        }
+
+                        if ui.add_enabled(!running, Button::new("Remove")).clicked() {
+                            remove = Some(subscription.id);
+                        }
+                    });
+                });
+        }
+
+        if let Some(id) = check_now
+            && let Some(subscription) = self.settings.subscriptions.iter_mut().find(|s| s.id == id)
+        {
+            subscription.last_checked = None;
+            self.subscriptions.retries.remove(&id);
+        }
+
+        if let Some(id) = remove {
+            self.settings.subscriptions.retain(|s| s.id != id);
+        }
+
+        ui.separator();
+        ui.label(RichText::new("New subscription").strong());
+        show_subscription_form(ui, &mut self.subscriptions.new_subscription, &preset_names);
+
+        let can_add = !self.subscriptions.new_subscription.name.is_empty()
+            && !self.subscriptions.new_subscription.url.is_empty()
+            && !self.subscriptions.new_subscription.output_dir.is_empty();
+
+        if ui.add_enabled(can_add, Button::new("Add")).clicked() {
+            let id = next_subscription_id(&self.settings);
+            let mut subscription = self.subscriptions.new_subscription.clone();
+            subscription.id = id;
+            self.settings.subscriptions.push(subscription);
+
+            self.subscriptions.new_subscription = Subscription::new(
+                id + 1,
+                &self.subscriptions.new_subscription.preset,
+                &self.subscriptions.new_subscription.output_dir,
+            );
+        }
+    }
+}
+
+fn show_subscription_form(ui: &mut egui::Ui, subscription: &mut Subscription, presets: &[String]) {
+    egui::Grid::new(("subscription_form", subscription.id))
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Name:");
+            ui.text_edit_singleline(&mut subscription.name);
+            ui.end_row();
+
+            ui.label("Channel/playlist URL:");
+            ui.text_edit_singleline(&mut subscription.url);
+            ui.end_row();
+
+            ui.label("Preset:");
+            ComboBox::new(("subscription_preset", subscription.id), "")
+                .selected_text(subscription.preset.as_str())
+                .show_ui(ui, |ui| {
+                    for preset in presets {
+                        ui.selectable_value(&mut subscription.preset, preset.clone(), preset);
+                    }
+                });
+            ui.end_row();
+
+            ui.label("Output directory:");
+            ui.horizontal(|ui| {
+                ui.text_edit_singleline(&mut subscription.output_dir);
+
+                if ui.button("Browse").clicked()
+                    && let Some(folder) = FileDialog::new()
+                        .set_directory(&subscription.output_dir)
+                        .pick_folder()
+                {
+                    subscription.output_dir = folder.to_string_lossy().to_string();
+                }
+            });
+            ui.end_row();
+
+            ui.label("Output template:");
+            ui.text_edit_singleline(&mut subscription.output_template);
+            ui.end_row();
+
+            ui.label("Schedule:");
+            ui.horizontal(|ui| {
+                let is_interval = matches!(subscription.schedule, Schedule::Interval { .. });
+
+                if ui.radio(is_interval, "Every").clicked() && !is_interval {
+                    subscription.schedule = Schedule::Interval { hours: 24 };
+                }
+                if ui.radio(!is_interval, "Daily at").clicked() && is_interval {
+                    subscription.schedule = Schedule::Daily { hour: 9, minute: 0 };
+                }
+
+                match &mut subscription.schedule {
+                    Schedule::Interval { hours } => {
+                        ui.add(DragValue::new(hours).range(1..=24 * 30).suffix(" h"));
+                    }
+                    Schedule::Daily { hour, minute } => {
+                        ui.add(DragValue::new(hour).range(0..=23));
+                        ui.label(":");
+                        ui.add(DragValue::new(minute).range(0..=59));
+                    }
+                }
+            });
+            ui.end_row();
+
+            ui.label("Enabled:");
+            ui.checkbox(&mut subscription.enabled, "");
+            ui.end_row();
+        });
+}